@@ -2,6 +2,7 @@ mod matchers;
 mod parsers;
 mod preliminaries;
 mod renderers;
+mod sanitizer;
 
 use matchers::{
     individual::IndividualMatcher,
@@ -9,61 +10,352 @@ use matchers::{
     utils::matcher::{LeftoverString, MatchedString, Matcher, RejectedString},
 };
 use parsers::{
+    block::leaf::link_reference_definition::LinkReference, utils::LinkLabel, ParseMultiple,
+};
+use preliminaries::{is_space_or_tab, normalize_input, Preliminaries, SourceMap};
+use renderers::{
+    normal_element::NormalElement, raw_element::RawElement, text_element::TextElement,
+    url::normalize_url, void_element::VoidElement, Render,
+};
+use sanitizer::TagBalancer;
+use std::{cell::RefCell, collections::HashMap};
+
+pub use parsers::{
     block::{
-        container::Container,
-        leaf::{atx_heading::AtxHeadingLevel, LeafCategory},
+        container::{list::ListSpacing, list_item::ListCategory, Container},
+        leaf::{
+            atx_heading::AtxHeadingLevel, setext_heading::SetextHeadingLevel, Leaf, LeafCategory,
+        },
         Block,
     },
     inline::{Inline, InlineCategory},
-    ParseMultiple,
 };
-use preliminaries::Preliminaries;
-use renderers::{
-    normal_element::NormalElement, text_element::TextElement, void_element::VoidElement, Render,
-};
-use std::collections::HashMap;
+pub use preliminaries::{Position, Span};
+pub use renderers::{void_element::VoidElementStyle, OutputFormat};
+pub use sanitizer::SanitizerPolicy;
+
+const NEWLINE: &str = "\n";
+
+// Added before block elements for each level of nesting, in the pretty output format.
+const PRETTY_INDENTATION: &str = "  ";
+
+// Replaces raw HTML in safe mode.
+const RAW_HTML_OMITTED: &str = "<!-- raw HTML omitted -->";
+
+// URL schemes that can run code or reach local files when followed, compared case-insensitively.
+const DANGEROUS_URL_SCHEMES: [&str; 4] = ["javascript:", "vbscript:", "file:", "data:"];
+
+// `data:` URLs of these image types are still allowed in safe mode, as long as the type is
+// followed by its parameters or data.
+const SAFE_DATA_URL_PREFIXES: [&str; 4] = [
+    "data:image/png",
+    "data:image/gif",
+    "data:image/jpeg",
+    "data:image/webp",
+];
 
 pub struct Document {
-    link_reference_map: HashMap<String, String>,
+    is_safe_mode: bool,
+    link_reference_map: HashMap<String, LinkReference>,
+    output_format: OutputFormat,
     preliminaries: Preliminaries,
+    source_map: SourceMap,
+    sanitizer_policy: Option<SanitizerPolicy>,
+    tag_balancer: RefCell<TagBalancer>,
+    void_element_style: VoidElementStyle,
 }
 
 impl Document {
     pub fn new() -> Document {
         Document {
+            is_safe_mode: false,
             link_reference_map: HashMap::new(),
+            output_format: OutputFormat::Spec,
             preliminaries: Preliminaries::initialize(),
+            source_map: SourceMap::default(),
+            sanitizer_policy: None,
+            tag_balancer: RefCell::new(TagBalancer::default()),
+            void_element_style: VoidElementStyle::Xhtml,
+        }
+    }
+
+    // In safe mode, raw HTML is omitted, and link and image destinations with a dangerous URL
+    // scheme are replaced with an empty destination, so that untrusted input can be rendered.
+    pub fn set_safe_mode(&mut self, is_safe_mode: bool) {
+        self.is_safe_mode = is_safe_mode;
+    }
+
+    // With a sanitizer policy, raw HTML is sanitized rather than passed through, or omitted in
+    // safe mode. Tags it leaves open are closed at the end of the block they're in.
+    pub fn set_sanitizer_policy(&mut self, optional_sanitizer_policy: Option<SanitizerPolicy>) {
+        self.sanitizer_policy = optional_sanitizer_policy;
+    }
+
+    pub fn set_output_format(&mut self, output_format: OutputFormat) {
+        self.output_format = output_format;
+    }
+
+    // Applies to every void element in the output, including those in sanitized raw HTML.
+    pub fn set_void_element_style(&mut self, void_element_style: VoidElementStyle) {
+        self.void_element_style = void_element_style;
+    }
+
+    // The entry point for getting the syntax tree rather than HTML. Parses the input into its
+    // block structure, with the inline content of headings and paragraphs parsed as well. Link references are resolved along the way, so rendering the
+    // result doesn't depend on the definitions.
+    pub fn parse(&mut self, input: String) -> Vec<Block> {
+        self.parse_bytes(input.as_bytes())
+    }
+
+    // For input that may not be valid UTF-8, such as uploaded files. Positions in the returned
+    // blocks refer to the input as given, before it is normalized.
+    pub fn parse_bytes(&mut self, input: &[u8]) -> Vec<Block> {
+        let (input, source_map) = normalize_input(input);
+        self.source_map = source_map;
+
+        let block_structure = Block::parse_multiple(input, self);
+        let (block_structure, definitions) =
+            Block::extract_link_reference_definitions(block_structure, self);
+
+        // When a label is defined more than once, the first definition takes precedence.
+        self.link_reference_map.clear();
+
+        for (label, link_reference) in definitions {
+            self.link_reference_map
+                .entry(LinkLabel::normalize(&label))
+                .or_insert(link_reference);
+        }
+
+        Block::parse_inlines(block_structure, self)
+    }
+
+    pub fn render(&mut self, input: String) -> String {
+        let block_structure = self.parse(input);
+
+        self.render_block_structure(block_structure)
+    }
+
+    // Renders blocks returned by `parse`, which may have been changed in between.
+    pub fn render_block_structure(&self, block_structure: Vec<Block>) -> String {
+        self.render_blocks(block_structure, 0)
+    }
+
+    pub fn render_bytes(&mut self, input: &[u8]) -> String {
+        let block_structure = self.parse_bytes(input);
+
+        self.render_block_structure(block_structure)
+    }
+
+    // Labels are matched after normalization, so the label doesn't need to be normalized already.
+    fn find_link_reference(&self, label: &str) -> Option<&LinkReference> {
+        self.link_reference_map.get(&LinkLabel::normalize(label))
+    }
+
+    fn render_raw_html(&self, html: String) -> RawElement {
+        match (&self.sanitizer_policy, self.is_safe_mode) {
+            (Some(sanitizer_policy), _) => RawElement(sanitizer_policy.sanitize(
+                &html,
+                self,
+                &mut self.tag_balancer.borrow_mut(),
+            )),
+            (None, true) => RawElement(String::from(RAW_HTML_OMITTED)),
+            (None, false) => RawElement(html),
+        }
+    }
+
+    // Closes the tags that sanitized raw HTML opened while rendering, but didn't close, so that
+    // they close inside the element being rendered.
+    fn render_balanced(&self, render: impl FnOnce() -> String) -> (String, String) {
+        if self.sanitizer_policy.is_none() {
+            return (render(), String::new());
+        }
+
+        self.tag_balancer.borrow_mut().enter();
+
+        let rendered = render();
+        let closing_tags = self.tag_balancer.borrow_mut().leave();
+
+        (rendered, closing_tags)
+    }
+
+    fn render_balanced_inlines(&self, inlines: Vec<Inline>) -> String {
+        let (rendered_inlines, closing_tags) =
+            self.render_balanced(|| self.render_inlines(inlines));
+
+        [rendered_inlines, closing_tags].concat()
+    }
+
+    // After blocks, the closing tags are laid out like a block of their own.
+    fn render_balanced_blocks(&self, render: impl FnOnce() -> String, depth: usize) -> String {
+        let (rendered_blocks, closing_tags) = self.render_balanced(render);
+
+        match closing_tags.is_empty() || !rendered_blocks.ends_with(NEWLINE) {
+            true => [rendered_blocks, closing_tags].concat(),
+            false => [rendered_blocks, self.format_block(closing_tags, depth)].concat(),
+        }
+    }
+
+    fn render_destination(&self, destination: String) -> String {
+        match self.is_safe_mode && is_dangerous_destination(&destination) {
+            true => String::new(),
+            false => normalize_url(&destination),
+        }
+    }
+
+    // Lays out a rendered block element, nested `depth` containers deep, for the output format.
+    fn format_block(&self, rendered_block: String, depth: usize) -> String {
+        let line_ending = match rendered_block.ends_with(NEWLINE) {
+            true => "",
+            false => NEWLINE,
+        };
+
+        match self.output_format {
+            OutputFormat::Compact => rendered_block,
+            OutputFormat::Pretty => [
+                PRETTY_INDENTATION.repeat(depth),
+                rendered_block,
+                String::from(line_ending),
+            ]
+            .concat(),
+            OutputFormat::Spec => [rendered_block, String::from(line_ending)].concat(),
+        }
+    }
+
+    // Lays out the rendered children of a container, which starts a new line in the output.
+    fn format_container_content(&self, rendered_children: String, depth: usize) -> String {
+        match self.output_format {
+            OutputFormat::Compact => rendered_children,
+            OutputFormat::Pretty => [
+                String::from(NEWLINE),
+                rendered_children,
+                PRETTY_INDENTATION.repeat(depth),
+            ]
+            .concat(),
+            OutputFormat::Spec => [String::from(NEWLINE), rendered_children].concat(),
         }
     }
 
-    pub fn render(&self, input: String) -> String {
-        let block_structure = Block::parse_multiple(input, &self);
+    fn render_blocks(&self, blocks: Vec<Block>, depth: usize) -> String {
+        self.render_balanced_blocks(
+            || {
+                let mut rendered_blocks = String::new();
 
-        self.render_blocks(block_structure)
+                for block in blocks {
+                    rendered_blocks = [rendered_blocks, self.render_block(block, depth)].concat();
+                }
+
+                rendered_blocks
+            },
+            depth,
+        )
     }
 
-    fn render_blocks(&self, blocks: Vec<Block>) -> String {
-        let mut rendered_blocks = String::new();
+    fn render_list_items(&self, items: Vec<Block>, spacing: ListSpacing, depth: usize) -> String {
+        let mut rendered_items = String::new();
+
+        for item in items {
+            let rendered_item = match item {
+                Block::Container(Container::ListItem(child_blocks), _) => {
+                    self.render_list_item(child_blocks, spacing, depth)
+                }
+                item => self.render_block(item, depth),
+            };
+
+            rendered_items = [rendered_items, rendered_item].concat();
+        }
+
+        rendered_items
+    }
+
+    // In tight lists, paragraphs directly inside list items aren't wrapped in `<p>` tags, and
+    // don't start a new line. Any other child block does, unless one was started already.
+    fn render_list_item(
+        &self,
+        child_blocks: Vec<Block>,
+        spacing: ListSpacing,
+        depth: usize,
+    ) -> String {
+        let mut rendered_child_blocks = self.render_balanced_blocks(
+            || {
+                let mut rendered_child_blocks = String::new();
+
+                for child_block in child_blocks {
+                    let rendered_child_block = match (child_block, spacing) {
+                        (Block::Leaf(leaf), ListSpacing::Tight)
+                            if leaf.category == LeafCategory::Paragraph =>
+                        {
+                            self.render_balanced_inlines(leaf.inlines)
+                        }
+                        (child_block, _) => {
+                            if self.output_format != OutputFormat::Compact
+                                && !rendered_child_blocks.ends_with(NEWLINE)
+                            {
+                                rendered_child_blocks.push_str(NEWLINE);
+                            }
+
+                            self.render_block(child_block, depth + 1)
+                        }
+                    };
+
+                    rendered_child_blocks = [rendered_child_blocks, rendered_child_block].concat();
+                }
 
-        for block in blocks {
-            rendered_blocks = [rendered_blocks, self.render_block(block)].concat();
+                rendered_child_blocks
+            },
+            depth + 1,
+        );
+
+        if self.output_format == OutputFormat::Pretty && rendered_child_blocks.ends_with(NEWLINE) {
+            rendered_child_blocks.push_str(&PRETTY_INDENTATION.repeat(depth));
         }
 
-        rendered_blocks
+        self.format_block(
+            NormalElement::new(String::from("li"), rendered_child_blocks).render(),
+            depth,
+        )
     }
 
-    fn render_block(&self, block: Block) -> String {
+    fn render_block(&self, block: Block, depth: usize) -> String {
         let renderer: Box<dyn Render> = match block {
-            Block::Container(container) => match container {
+            Block::Container(container, _) => match container {
                 Container::BlockQuote(child_blocks) => Box::new(NormalElement::new(
                     String::from("blockquote"),
-                    self.render_blocks(child_blocks),
+                    self.format_container_content(
+                        self.render_blocks(child_blocks, depth + 1),
+                        depth,
+                    ),
                 )),
+                Container::List(category, spacing, items) => {
+                    let rendered_items = self.format_container_content(
+                        self.render_list_items(items, spacing, depth + 1),
+                        depth,
+                    );
+
+                    match category {
+                        ListCategory::Bullet(_) => {
+                            Box::new(NormalElement::new(String::from("ul"), rendered_items))
+                        }
+                        ListCategory::Ordered(start, _) => {
+                            let mut ordered_list_element =
+                                NormalElement::new(String::from("ol"), rendered_items);
+
+                            if start != 1 {
+                                ordered_list_element
+                                    .add_attribute(String::from("start"), start.to_string());
+                            }
+
+                            Box::new(ordered_list_element)
+                        }
+                    }
+                }
+                Container::ListItem(child_blocks) => {
+                    return self.render_list_item(child_blocks, ListSpacing::Loose, depth)
+                }
             },
 
             Block::Leaf(leaf) => match leaf.category {
                 LeafCategory::AtxHeading(atx_heading_level) => {
-                    let inner_text = self.render_inlines(leaf.text);
+                    let inner_text = self.render_balanced_inlines(leaf.inlines);
 
                     match atx_heading_level {
                         AtxHeadingLevel::One => {
@@ -86,21 +378,60 @@ impl Document {
                         }
                     }
                 }
-                LeafCategory::ThematicBreak => Box::new(VoidElement::new(String::from("hr"))),
+                LeafCategory::SetextHeading(setext_heading_level) => {
+                    let inner_text = self.render_balanced_inlines(leaf.inlines);
+
+                    match setext_heading_level {
+                        SetextHeadingLevel::One => {
+                            Box::new(NormalElement::new(String::from("h1"), inner_text))
+                        }
+                        SetextHeadingLevel::Two => {
+                            Box::new(NormalElement::new(String::from("h2"), inner_text))
+                        }
+                    }
+                }
+                LeafCategory::IndentedCodeBlock => Box::new(NormalElement::new(
+                    String::from("pre"),
+                    NormalElement::new(String::from("code"), TextElement(leaf.text).render())
+                        .render(),
+                )),
+                LeafCategory::FencedCodeBlock(optional_info_string) => {
+                    let mut code_element =
+                        NormalElement::new(String::from("code"), TextElement(leaf.text).render());
+
+                    // Only the first word of the info string is used, as the language.
+                    if let Some(language) = optional_info_string
+                        .as_ref()
+                        .and_then(|info_string| info_string.split(is_space_or_tab).next())
+                    {
+                        code_element
+                            .add_attribute(String::from("class"), format!("language-{}", language));
+                    }
+
+                    Box::new(NormalElement::new(
+                        String::from("pre"),
+                        code_element.render(),
+                    ))
+                }
+                LeafCategory::HtmlBlock => Box::new(self.render_raw_html(leaf.text)),
+                LeafCategory::ThematicBreak => Box::new(VoidElement::new(
+                    String::from("hr"),
+                    self.void_element_style,
+                )),
                 LeafCategory::Paragraph => Box::new(NormalElement::new(
                     String::from("p"),
-                    self.render_inlines(leaf.text),
+                    self.render_balanced_inlines(leaf.inlines),
                 )),
             },
         };
 
-        renderer.render()
+        self.format_block(renderer.render(), depth)
     }
 
-    fn render_inlines(&self, text: String) -> String {
+    fn render_inlines(&self, inlines: Vec<Inline>) -> String {
         let mut rendered_inlines = String::new();
 
-        for inline in Inline::parse_multiple(text, &self) {
+        for inline in inlines {
             rendered_inlines = [rendered_inlines, self.render_inline(inline)].concat();
         }
 
@@ -109,14 +440,451 @@ impl Document {
 
     fn render_inline(&self, inline: Inline) -> String {
         let renderer: Box<dyn Render> = match inline.category {
-            InlineCategory::CodeSpan => {
-                Box::new(NormalElement::new(String::from("code"), inline.text))
+            InlineCategory::CodeSpan => Box::new(NormalElement::new(
+                String::from("code"),
+                TextElement(inline.text).render(),
+            )),
+            InlineCategory::Emphasis(children) => Box::new(NormalElement::new(
+                String::from("em"),
+                self.render_balanced_inlines(children),
+            )),
+            InlineCategory::HardBreak => Box::new(RawElement(
+                [
+                    VoidElement::new(String::from("br"), self.void_element_style).render(),
+                    String::from("\n"),
+                ]
+                .concat(),
+            )),
+            InlineCategory::Image(source, optional_title, children) => {
+                let mut image_element =
+                    VoidElement::new(String::from("img"), self.void_element_style);
+
+                image_element.add_attribute(String::from("src"), self.render_destination(source));
+                image_element.add_attribute(String::from("alt"), self.render_plain_text(children));
+
+                if let Some(title) = optional_title {
+                    image_element.add_attribute(String::from("title"), title);
+                }
+
+                Box::new(image_element)
             }
+            InlineCategory::Link(destination, optional_title, children) => {
+                let mut link_element =
+                    NormalElement::new(String::from("a"), self.render_balanced_inlines(children));
+
+                link_element
+                    .add_attribute(String::from("href"), self.render_destination(destination));
+
+                if let Some(title) = optional_title {
+                    link_element.add_attribute(String::from("title"), title);
+                }
+
+                Box::new(link_element)
+            }
+            InlineCategory::RawHtml => Box::new(self.render_raw_html(inline.text)),
+            InlineCategory::SoftBreak => Box::new(TextElement(String::from("\n"))),
+            InlineCategory::StrongEmphasis(children) => Box::new(NormalElement::new(
+                String::from("strong"),
+                self.render_balanced_inlines(children),
+            )),
             InlineCategory::TextualContent => Box::new(TextElement(inline.text)),
         };
 
         renderer.render()
     }
+
+    // Images are described by the text content of their description only, without any markup.
+    fn render_plain_text(&self, inlines: Vec<Inline>) -> String {
+        let mut rendered_text = String::new();
+
+        for inline in inlines {
+            let rendered_inline = match inline.category {
+                InlineCategory::CodeSpan
+                | InlineCategory::RawHtml
+                | InlineCategory::TextualContent => inline.text,
+                InlineCategory::HardBreak | InlineCategory::SoftBreak => String::from("\n"),
+                InlineCategory::Emphasis(children)
+                | InlineCategory::Image(_, _, children)
+                | InlineCategory::Link(_, _, children)
+                | InlineCategory::StrongEmphasis(children) => self.render_plain_text(children),
+            };
+
+            rendered_text = [rendered_text, rendered_inline].concat();
+        }
+
+        rendered_text
+    }
 }
 
-// TODO - tests
+// [SPEC]: https://spec.commonmark.org/0.30/#security
+fn is_dangerous_destination(destination: &str) -> bool {
+    let starts_with_ignoring_case = |prefix: &str| {
+        destination
+            .get(..prefix.len())
+            .is_some_and(|start| start.eq_ignore_ascii_case(prefix))
+    };
+
+    DANGEROUS_URL_SCHEMES
+        .iter()
+        .any(|scheme| starts_with_ignoring_case(scheme))
+        && !SAFE_DATA_URL_PREFIXES.iter().any(|prefix| {
+            starts_with_ignoring_case(prefix)
+                && matches!(destination.as_bytes().get(prefix.len()), Some(b';' | b','))
+        })
+}
+
+impl Default for Document {
+    fn default() -> Document {
+        Document::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        is_dangerous_destination, AtxHeadingLevel, Block, Container, Document, Inline,
+        InlineCategory, Leaf, LeafCategory, LinkReference, ListCategory, ListSpacing, OutputFormat,
+        Position, SanitizerPolicy, Span,
+    };
+
+    #[test]
+    fn it_collects_link_reference_definitions_under_normalized_labels() {
+        let mut document = Document::new();
+
+        assert_eq!(
+            document.render(String::from(
+                "[Foo\n  Bar]: /url \"title\"\n\n> - [baz]: /baz\n"
+            )),
+            "<blockquote>\n<ul>\n<li></li>\n</ul>\n</blockquote>\n"
+        );
+        assert_eq!(
+            document.link_reference_map.get("FOO BAR"),
+            Some(&LinkReference {
+                destination: String::from("/url"),
+                title: Some(String::from("title")),
+            })
+        );
+        assert!(document.link_reference_map.contains_key("BAZ"));
+    }
+
+    #[test]
+    fn it_keeps_first_of_duplicate_link_reference_definitions() {
+        let mut document = Document::new();
+
+        document.render(String::from("[foo]: /first\n[FOO]: /second\n"));
+
+        assert_eq!(
+            document
+                .link_reference_map
+                .get("FOO")
+                .map(|link_reference| link_reference.destination.as_str()),
+            Some("/first")
+        );
+    }
+
+    #[test]
+    fn it_clears_link_reference_definitions_between_renders() {
+        let mut document = Document::new();
+
+        document.render(String::from("[foo]: /url\n"));
+        document.render(String::from("bar\n"));
+
+        assert!(document.link_reference_map.is_empty());
+    }
+
+    #[test]
+    fn it_reads_underline_after_only_definitions_as_other_line() {
+        let mut document = Document::new();
+
+        assert_eq!(
+            document.render(String::from("[foo]: /url\n===\n[foo]\n")),
+            "<p>===\n<a href=\"/url\">foo</a></p>\n"
+        );
+        assert_eq!(
+            document.render(String::from("[foo]: /url\n===\n")),
+            "<p>===</p>\n"
+        );
+        assert_eq!(
+            document.render(String::from("[foo]: /url\n---\n")),
+            "<hr />\n"
+        );
+    }
+
+    #[test]
+    fn it_parses_into_typed_block_structure_with_inlines() {
+        let mut document = Document::new();
+
+        let block_structure = document.parse(String::from(
+            "## *a*\n\n1. [b]\n\n```rust\nc\n```\n\n[b]: /url \"title\"\n",
+        ));
+
+        let span = |start, end| document.source_map.span(start, end);
+
+        let mut heading = Leaf::new(
+            LeafCategory::AtxHeading(AtxHeadingLevel::Two),
+            String::from("*a*"),
+        )
+        .with_source(span(0, 6), vec![(0, 3)]);
+        heading.inlines = vec![Inline::new(
+            InlineCategory::Emphasis(vec![Inline::new(
+                InlineCategory::TextualContent,
+                String::from("a"),
+            )
+            .with_span(span(4, 5))]),
+            String::new(),
+        )
+        .with_span(span(3, 6))];
+
+        let mut paragraph = Leaf::new(LeafCategory::Paragraph, String::from("[b]"))
+            .with_source(span(11, 14), vec![(0, 11)]);
+        paragraph.inlines = vec![Inline::new(
+            InlineCategory::Link(
+                String::from("/url"),
+                Some(String::from("title")),
+                vec![
+                    Inline::new(InlineCategory::TextualContent, String::from("b"))
+                        .with_span(span(12, 13)),
+                ],
+            ),
+            String::new(),
+        )
+        .with_span(span(11, 14))];
+
+        assert_eq!(
+            block_structure,
+            vec![
+                Block::Leaf(heading),
+                Block::Container(
+                    Container::List(
+                        ListCategory::Ordered(1, '.'),
+                        ListSpacing::Tight,
+                        vec![Block::Container(
+                            Container::ListItem(vec![Block::Leaf(paragraph)]),
+                            span(8, 14)
+                        )]
+                    ),
+                    span(8, 14)
+                ),
+                Block::Leaf(
+                    Leaf::new(
+                        LeafCategory::FencedCodeBlock(Some(String::from("rust"))),
+                        String::from("c\n")
+                    )
+                    .with_source(span(16, 29), Vec::new())
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_locates_nodes_in_the_original_input() {
+        let mut document = Document::new();
+
+        let block_structure = document.parse_bytes("\u{FEFF}> [a]: /u\r\n> x *b*\r\n".as_bytes());
+
+        let position = |offset, line, column| Position {
+            offset,
+            line,
+            column,
+        };
+
+        assert_eq!(
+            block_structure[0].span(),
+            Span {
+                start: position(3, 1, 4),
+                end: position(21, 2, 8),
+            }
+        );
+
+        let Block::Container(Container::BlockQuote(child_blocks), _) = &block_structure[0] else {
+            panic!("expected a block quote, got {:?}", block_structure[0]);
+        };
+        let Block::Leaf(paragraph) = &child_blocks[0] else {
+            panic!("expected a paragraph, got {:?}", child_blocks[0]);
+        };
+
+        assert_eq!(
+            paragraph.span,
+            Span {
+                start: position(16, 2, 3),
+                end: position(21, 2, 8),
+            }
+        );
+        assert_eq!(
+            paragraph.inlines[1].span,
+            Span {
+                start: position(18, 2, 5),
+                end: position(21, 2, 8),
+            }
+        );
+    }
+
+    #[test]
+    fn it_renders_changed_block_structure() {
+        let mut document = Document::new();
+
+        let mut block_structure = document.parse(String::from("# a\n\nb\n"));
+        block_structure.remove(0);
+
+        assert_eq!(
+            document.render_block_structure(block_structure),
+            "<p>b</p>\n"
+        );
+    }
+
+    #[test]
+    fn it_normalizes_line_endings_in_every_block() {
+        let mut document = Document::new();
+
+        assert_eq!(
+            document.render(String::from("a\r\nb\r\n\r```\r\nc\r\n```\r\n<div>\rd\r\n")),
+            "<p>a\nb</p>\n<pre><code>c\n</code></pre>\n<div>\nd\n"
+        );
+    }
+
+    #[test]
+    fn it_renders_bytes_that_are_not_valid_utf8() {
+        let mut document = Document::new();
+
+        assert_eq!(
+            document.render_bytes(b"\xef\xbb\xbf# a\xff\x00b"),
+            "<h1>a\u{FFFD}\u{FFFD}b</h1>\n"
+        );
+    }
+
+    #[test]
+    fn it_omits_raw_html_in_safe_mode() {
+        let mut document = Document::new();
+
+        document.set_safe_mode(true);
+
+        assert_eq!(
+            document.render(String::from("<div>\n*foo*\n</div>\n\nbar <em>baz</em>")),
+            "<!-- raw HTML omitted -->\n<p>bar <!-- raw HTML omitted -->baz<!-- raw HTML omitted --></p>\n"
+        );
+    }
+
+    #[test]
+    fn it_blanks_dangerous_destinations_in_safe_mode() {
+        let mut document = Document::new();
+
+        document.set_safe_mode(true);
+
+        assert_eq!(
+            document.render(String::from(
+                "[a](javascript:alert(1)) ![b](data:image/png;base64,AA) ![c](DATA:text/html,x)"
+            )),
+            "<p><a href=\"\">a</a> <img src=\"data:image/png;base64,AA\" alt=\"b\" /> <img src=\"\" alt=\"c\" /></p>\n"
+        );
+    }
+
+    #[test]
+    fn it_keeps_raw_html_and_destinations_outside_safe_mode() {
+        let mut document = Document::new();
+
+        assert_eq!(
+            document.render(String::from("<b>[a](javascript:alert(1))</b>")),
+            "<p><b><a href=\"javascript:alert(1)\">a</a></b></p>\n"
+        );
+    }
+
+    #[test]
+    fn it_closes_sanitized_tags_inside_the_inline_that_opened_them() {
+        let mut document = Document::new();
+
+        document.set_sanitizer_policy(Some(SanitizerPolicy::new()));
+
+        assert_eq!(
+            document.render(String::from("*<b>a*</b>")),
+            "<p><em><b>a</b></em></p>\n"
+        );
+        assert_eq!(
+            document.render(String::from("[<b>x](/u) y</b>")),
+            "<p><a href=\"/u\"><b>x</b></a> y</p>\n"
+        );
+        assert_eq!(
+            document.render(String::from("<b>**a</b>**")),
+            "<p><b><strong>a</strong></b></p>\n"
+        );
+    }
+
+    #[test]
+    fn it_closes_sanitized_tags_left_open_in_a_container_on_their_own_line() {
+        let mut document = Document::new();
+
+        document.set_sanitizer_policy(Some(SanitizerPolicy::new()));
+
+        assert_eq!(
+            document.render(String::from("> <div>\n> a")),
+            "<blockquote>\n<div>\na\n</div>\n</blockquote>\n"
+        );
+
+        document.set_output_format(OutputFormat::Pretty);
+
+        assert_eq!(
+            document.render(String::from("- <div>\n  a")),
+            "<ul>\n  <li>\n    <div>\na\n    </div>\n  </li>\n</ul>\n"
+        );
+    }
+
+    #[test]
+    fn it_detects_dangerous_destinations() {
+        for destination in [
+            "javascript:alert(1)",
+            "JavaScript:alert(1)",
+            "vbscript:msgbox",
+            "file:///etc/passwd",
+            "data:text/html;base64,PHNjcmlwdD4=",
+            "data:image/svg+xml,<svg/>",
+            "data:image/pngfoo,AA",
+            "data:image/png",
+        ] {
+            assert!(is_dangerous_destination(destination));
+        }
+
+        for destination in [
+            "https://example.com",
+            "/javascript:",
+            "data:image/gif;base64,R0lG",
+            "DATA:IMAGE/JPEG;base64,/9j/",
+            "data:image/webp,AA",
+            "",
+        ] {
+            assert!(!is_dangerous_destination(destination));
+        }
+    }
+
+    #[test]
+    fn it_renders_in_spec_output_format_by_default() {
+        let mut document = Document::new();
+
+        assert_eq!(
+            document.render(String::from("> - a\n>   - b\n>\n> c\n\n```\nd\n```")),
+            "<blockquote>\n<ul>\n<li>a\n<ul>\n<li>b</li>\n</ul>\n</li>\n</ul>\n<p>c</p>\n</blockquote>\n<pre><code>d\n</code></pre>\n"
+        );
+    }
+
+    #[test]
+    fn it_renders_in_compact_output_format() {
+        let mut document = Document::new();
+
+        document.set_output_format(OutputFormat::Compact);
+
+        assert_eq!(
+            document.render(String::from("> - a\n>   - b\n>\n> c\n\n```\nd\n```")),
+            "<blockquote><ul><li>a<ul><li>b</li></ul></li></ul><p>c</p></blockquote><pre><code>d\n</code></pre>"
+        );
+    }
+
+    #[test]
+    fn it_renders_in_pretty_output_format() {
+        let mut document = Document::new();
+
+        document.set_output_format(OutputFormat::Pretty);
+
+        assert_eq!(
+            document.render(String::from("> - a\n>   - b\n>\n> c\n\n```\nd\n```")),
+            "<blockquote>\n  <ul>\n    <li>a\n      <ul>\n        <li>b</li>\n      </ul>\n    </li>\n  </ul>\n  <p>c</p>\n</blockquote>\n<pre><code>d\n</code></pre>\n"
+        );
+    }
+}
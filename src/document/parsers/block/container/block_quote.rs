@@ -1,225 +1,116 @@
-use super::{
-    Document, Leaf, LeafCategory, Matcher, Parse, ParseResult, SelectionMatcher, TryParse,
-    TryParseResult,
-};
+use super::{Document, IndividualMatcher, Matcher, CODE_BLOCK_INDENTATION};
 
+const BLOCK_QUOTE_DELIMITER: char = '>';
+
+// [SPEC]: https://spec.commonmark.org/0.30/#block-quotes
 pub struct BlockQuote;
 
-impl TryParse<String> for BlockQuote {
-    fn try_parse(input: String, document: &Document) -> TryParseResult<String> {
-        let delimiter_matcher = SelectionMatcher::from(vec![String::from("> "), String::from('>')]);
-
-        let mut is_block_quote = false;
-
-        let mut consumed = String::new();
-        let mut unconsumed = input;
-
-        let mut leading_space_count = 0;
-
-        while !unconsumed.is_empty() {
-            if !is_block_quote {
-                match delimiter_matcher.try_match(unconsumed) {
-                    Ok((_, leftover_from_matched_delimiter)) => {
-                        unconsumed = leftover_from_matched_delimiter;
-                        is_block_quote = true;
-
-                        if leading_space_count > 0 {
-                            consumed = String::new();
-                        }
-
-                        continue;
-                    }
-                    Err(rejected_from_delimiter_matcher) => {
-                        if leading_space_count < 3 {
-                            match document
-                                .preliminaries
-                                .space
-                                .try_match(rejected_from_delimiter_matcher)
-                            {
-                                Ok((matched_space, leftover_from_matched_space)) => {
-                                    leading_space_count = leading_space_count + 1;
-                                    consumed = [consumed, matched_space].concat();
-                                    unconsumed = leftover_from_matched_space;
-                                    continue;
-                                }
-                                Err(rejected_from_space_matcher) => {
-                                    unconsumed = rejected_from_space_matcher;
-                                    break;
-                                }
-                            }
-                        } else {
-                            unconsumed = rejected_from_delimiter_matcher;
-                            break;
-                        }
-                    }
-                }
-            } else {
-                match document.preliminaries.line.try_match(unconsumed) {
-                    Ok((matched_line, leftover_from_line_matcher)) => {
-                        match document.preliminaries.blank_line.try_match(matched_line) {
-                            Ok((_, leftover_from_blank_line_matcher)) => {
-                                unconsumed = leftover_from_blank_line_matcher;
-                                break;
-                            }
-                            Err(rejected_from_blank_line_matcher) => {
-                                match delimiter_matcher.try_match(rejected_from_blank_line_matcher)
-                                {
-                                    Ok((_, leftover_from_matched_delimiter)) => {
-                                        consumed =
-                                            [consumed, leftover_from_matched_delimiter].concat();
-                                        unconsumed = leftover_from_line_matcher;
-                                        continue;
-                                    }
-                                    Err(rejected_from_delimiter_matcher) => {
-                                        let ParseResult(leaf, optional_leftover_from_leaf_parser) =
-                                            Leaf::parse(rejected_from_delimiter_matcher, document);
-
-                                        if leaf.category == LeafCategory::Paragraph {
-                                            consumed = [consumed, leaf.text].concat();
-                                            unconsumed = match optional_leftover_from_leaf_parser {
-                                                Some(leftover_from_leaf_parser) => [
-                                                    leftover_from_leaf_parser,
-                                                    leftover_from_line_matcher,
-                                                ]
-                                                .concat(),
-                                                None => leftover_from_line_matcher,
-                                            };
-
-                                            continue;
-                                        } else {
-                                            unconsumed = match optional_leftover_from_leaf_parser {
-                                                Some(leftover_from_leaf_parser) => [
-                                                    leaf.text,
-                                                    leftover_from_leaf_parser,
-                                                    leftover_from_line_matcher,
-                                                ]
-                                                .concat(),
-                                                None => {
-                                                    [leaf.text, leftover_from_line_matcher].concat()
-                                                }
-                                            };
-                                            break;
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    }
-                    Err(rejected_from_line_matcher) => {
-                        unconsumed = rejected_from_line_matcher;
-                        break;
-                    }
+impl BlockQuote {
+    // Strips the delimiter, which can be indented by up to three columns, along with the optional
+    // space after it. When that space is part of a tab, the rest of the tab is left as spaces.
+    pub fn strip_delimiter(line: String, document: &Document) -> Result<String, String> {
+        let delimiter_matcher = IndividualMatcher::from(BLOCK_QUOTE_DELIMITER);
+
+        let (matched_indentation, unconsumed) =
+            match document.preliminaries.indentation.try_match(line) {
+                Ok(matched) => matched,
+                Err(rejected_from_indentation_matcher) => {
+                    (String::new(), rejected_from_indentation_matcher)
                 }
-            }
+            };
+
+        let indentation_width = document
+            .preliminaries
+            .indentation
+            .width(&matched_indentation, 0);
+
+        // With four or more columns of indentation, the line belongs to an indented code block.
+        if indentation_width >= CODE_BLOCK_INDENTATION {
+            return Err([matched_indentation, unconsumed].concat());
         }
 
-        if is_block_quote {
-            return TryParseResult::Accepted(ParseResult::new(consumed, unconsumed));
-        } else {
-            return TryParseResult::Rejected([consumed, unconsumed].concat());
+        match delimiter_matcher.try_match(unconsumed) {
+            Ok((_, leftover_from_matched_delimiter)) => Ok(document
+                .preliminaries
+                .indentation
+                .strip(&leftover_from_matched_delimiter, indentation_width + 1, 1)),
+            Err(rejected_from_delimiter_matcher) => {
+                Err([matched_indentation, rejected_from_delimiter_matcher].concat())
+            }
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{BlockQuote, Document, ParseResult, TryParse, TryParseResult};
+    use super::{BlockQuote, Document};
 
     #[test]
-    fn it_accepts_simple_example() {
+    fn it_strips_delimiter_and_following_space() {
         let document = Document::new();
 
         assert_eq!(
-            BlockQuote::try_parse(String::from("> # Foo\n> bar\n> baz"), &document),
-            TryParseResult::Accepted(ParseResult(String::from("# Foo\nbar\nbaz"), None))
+            BlockQuote::strip_delimiter(String::from("> # Foo\n"), &document),
+            Ok(String::from("# Foo\n"))
         );
-    }
-
-    #[test]
-    fn it_accepts_omitted_space_after_delimiter() {
-        let document = Document::new();
-
-        assert_eq!(
-            BlockQuote::try_parse(String::from("> # Foo\n>bar\n> baz"), &document),
-            TryParseResult::Accepted(ParseResult(String::from("# Foo\nbar\nbaz"), None))
-        );
-    }
-
-    #[test]
-    fn it_accepts_one_leading_space() {
-        let document = Document::new();
 
         assert_eq!(
-            BlockQuote::try_parse(String::from(" > # Foo"), &document),
-            TryParseResult::Accepted(ParseResult(String::from("# Foo"), None))
+            BlockQuote::strip_delimiter(String::from(">bar"), &document),
+            Ok(String::from("bar"))
         );
-    }
-
-    #[test]
-    fn it_accepts_two_leading_spaces() {
-        let document = Document::new();
 
         assert_eq!(
-            BlockQuote::try_parse(String::from("  > # Foo"), &document),
-            TryParseResult::Accepted(ParseResult(String::from("# Foo"), None))
+            BlockQuote::strip_delimiter(String::from(">  code"), &document),
+            Ok(String::from(" code"))
         );
     }
 
     #[test]
-    fn it_accepts_three_leading_spaces() {
+    fn it_accepts_up_to_three_spaces_of_indentation() {
         let document = Document::new();
 
-        assert_eq!(
-            BlockQuote::try_parse(String::from("   > # Foo"), &document),
-            TryParseResult::Accepted(ParseResult(String::from("# Foo"), None))
-        );
+        for line in [" > # Foo", "  > # Foo", "   > # Foo"] {
+            assert_eq!(
+                BlockQuote::strip_delimiter(String::from(line), &document),
+                Ok(String::from("# Foo"))
+            );
+        }
     }
 
     #[test]
-    fn it_rejects_four_or_more_leading_spaces() {
+    fn it_rejects_four_or_more_spaces_of_indentation() {
         let document = Document::new();
 
-        assert_eq!(
-            BlockQuote::try_parse(String::from("    > # Foo"), &document),
-            TryParseResult::Rejected(String::from("    > # Foo"))
-        );
-
-        assert_eq!(
-            BlockQuote::try_parse(String::from("     > # Foo"), &document),
-            TryParseResult::Rejected(String::from("     > # Foo"))
-        );
+        for line in ["    > # Foo", "     > # Foo", "\t> # Foo"] {
+            assert_eq!(
+                BlockQuote::strip_delimiter(String::from(line), &document),
+                Err(String::from(line))
+            );
+        }
     }
 
     #[test]
-    fn it_accepts_lazy_continuation_lines() {
+    fn it_rejects_lines_without_delimiter() {
         let document = Document::new();
 
         assert_eq!(
-            BlockQuote::try_parse(String::from("> # Foo\nbar\nbaz"), &document),
-            TryParseResult::Accepted(ParseResult(String::from("# Foo\nbar\nbaz"), None))
+            BlockQuote::strip_delimiter(String::from("  foo"), &document),
+            Err(String::from("  foo"))
         );
     }
 
     #[test]
-    fn it_accepts_some_lazy_continuation_lines_and_some_delimiters() {
+    fn it_leaves_remaining_columns_of_tab_after_delimiter_as_spaces() {
         let document = Document::new();
 
         assert_eq!(
-            BlockQuote::try_parse(String::from("> # Foo\nbar\n>baz"), &document),
-            TryParseResult::Accepted(ParseResult(String::from("# Foo\nbar\nbaz"), None))
+            BlockQuote::strip_delimiter(String::from(">\t\tfoo"), &document),
+            Ok(String::from("      foo"))
         );
-    }
-
-    #[test]
-    fn it_rejects_lazy_continuation_lines_for_non_paragraph_continuation_text() {
-        let document = Document::new();
 
         assert_eq!(
-            BlockQuote::try_parse(String::from("> # Foo\n---"), &document),
-            TryParseResult::Accepted(ParseResult(
-                String::from("# Foo\n"),
-                Some(String::from("---"))
-            ))
+            BlockQuote::strip_delimiter(String::from(" >\tfoo"), &document),
+            Ok(String::from(" foo"))
         );
     }
 }
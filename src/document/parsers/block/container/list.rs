@@ -0,0 +1,6 @@
+// [SPEC]: https://spec.commonmark.org/0.30/#loose
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ListSpacing {
+    Tight,
+    Loose,
+}
@@ -0,0 +1,243 @@
+use super::{Document, Matcher, ParseResult, TryParse, TryParseResult, CODE_BLOCK_INDENTATION};
+use crate::document::preliminaries::{is_line_ending, is_space_or_tab};
+
+const BULLET_LIST_MARKERS: [char; 3] = ['-', '+', '*'];
+const ORDERED_LIST_DELIMITERS: [char; 2] = ['.', ')'];
+const MAXIMUM_ORDERED_LIST_MARKER_DIGITS: usize = 9;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ListCategory {
+    Bullet(char),
+    Ordered(u32, char),
+}
+
+impl ListCategory {
+    // Two list items belong to the same list if they use the same bullet character, or the same
+    // delimiter after their number.
+    pub fn is_same_list_as(&self, other: &ListCategory) -> bool {
+        match (self, other) {
+            (ListCategory::Bullet(marker), ListCategory::Bullet(other_marker)) => {
+                marker == other_marker
+            }
+            (ListCategory::Ordered(_, delimiter), ListCategory::Ordered(_, other_delimiter)) => {
+                delimiter == other_delimiter
+            }
+            _ => false,
+        }
+    }
+}
+
+// [SPEC]: https://spec.commonmark.org/0.30/#list-marker
+pub struct ListMarker;
+
+impl ListMarker {
+    // A list item can only interrupt a paragraph if it isn't empty and, for ordered lists, if it
+    // starts with 1.
+    pub fn can_interrupt_paragraph(line: String, document: &Document) -> bool {
+        match ListMarker::try_parse(line, document) {
+            TryParseResult::Accepted(ParseResult((category, _), optional_leftover)) => {
+                let is_empty = optional_leftover
+                    .map(|leftover| {
+                        document
+                            .preliminaries
+                            .blank_line
+                            .try_match(leftover)
+                            .is_ok()
+                    })
+                    .unwrap_or(true);
+
+                !is_empty
+                    && matches!(
+                        category,
+                        ListCategory::Bullet(_) | ListCategory::Ordered(1, _)
+                    )
+            }
+            TryParseResult::Rejected(_) => false,
+        }
+    }
+}
+
+// Accepts the marker of a list item's first line, along with the indentation its content needs
+// on subsequent lines. The leftover starts with the content of the first line.
+impl TryParse<(ListCategory, usize)> for ListMarker {
+    fn try_parse(input: String, document: &Document) -> TryParseResult<(ListCategory, usize)> {
+        let (matched_indentation, unconsumed) =
+            match document.preliminaries.indentation.try_match(input) {
+                Ok(matched) => matched,
+                Err(rejected_from_indentation_matcher) => {
+                    (String::new(), rejected_from_indentation_matcher)
+                }
+            };
+
+        let indentation_width = document
+            .preliminaries
+            .indentation
+            .width(&matched_indentation, 0);
+
+        if indentation_width >= CODE_BLOCK_INDENTATION {
+            return TryParseResult::Rejected([matched_indentation, unconsumed].concat());
+        }
+
+        let digits: String = unconsumed
+            .chars()
+            .take_while(|character| character.is_ascii_digit())
+            .collect();
+
+        let (category, marker_width) = match unconsumed[digits.len()..].chars().next() {
+            Some(marker) if digits.is_empty() && BULLET_LIST_MARKERS.contains(&marker) => {
+                (ListCategory::Bullet(marker), 1)
+            }
+            Some(delimiter)
+                if !digits.is_empty()
+                    && digits.len() <= MAXIMUM_ORDERED_LIST_MARKER_DIGITS
+                    && ORDERED_LIST_DELIMITERS.contains(&delimiter) =>
+            {
+                match digits.parse() {
+                    Ok(start) => (ListCategory::Ordered(start, delimiter), digits.len() + 1),
+                    Err(_) => {
+                        return TryParseResult::Rejected([matched_indentation, unconsumed].concat())
+                    }
+                }
+            }
+            _ => return TryParseResult::Rejected([matched_indentation, unconsumed].concat()),
+        };
+
+        // Tabs after the marker advance to tab stops counted from the start of the line.
+        let marker_end_column = indentation_width + marker_width;
+        let after_marker = &unconsumed[marker_width..];
+        let after_spaces = after_marker.trim_start_matches(is_space_or_tab);
+        let space_width = document
+            .preliminaries
+            .indentation
+            .width(after_marker, marker_end_column);
+
+        let is_blank = after_spaces.is_empty() || after_spaces.starts_with(is_line_ending);
+
+        if space_width == 0 && !is_blank {
+            return TryParseResult::Rejected([matched_indentation, unconsumed].concat());
+        }
+
+        // An item starting with a blank line, or with an indented code block, has its content
+        // indented by a single column after the marker.
+        let content_space_width = if is_blank || space_width > CODE_BLOCK_INDENTATION {
+            1
+        } else {
+            space_width
+        };
+
+        let content_indentation = marker_end_column + content_space_width;
+        let leftover = if is_blank {
+            String::from(after_spaces)
+        } else {
+            document.preliminaries.indentation.strip(
+                after_marker,
+                marker_end_column,
+                content_space_width,
+            )
+        };
+
+        TryParseResult::Accepted(ParseResult::new((category, content_indentation), leftover))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Document, ListCategory, ListMarker, ParseResult, TryParse, TryParseResult};
+
+    #[test]
+    fn it_accepts_bullet_and_ordered_markers() {
+        let document = Document::new();
+
+        assert_eq!(
+            ListMarker::try_parse(String::from("- foo"), &document),
+            TryParseResult::Accepted(ParseResult(
+                (ListCategory::Bullet('-'), 2),
+                Some(String::from("foo"))
+            ))
+        );
+
+        assert_eq!(
+            ListMarker::try_parse(String::from("  10)  foo"), &document),
+            TryParseResult::Accepted(ParseResult(
+                (ListCategory::Ordered(10, ')'), 7),
+                Some(String::from("foo"))
+            ))
+        );
+    }
+
+    #[test]
+    fn it_rejects_invalid_markers() {
+        let document = Document::new();
+
+        assert_eq!(
+            ListMarker::try_parse(String::from("-foo"), &document),
+            TryParseResult::Rejected(String::from("-foo"))
+        );
+
+        assert_eq!(
+            ListMarker::try_parse(String::from("1234567890. foo"), &document),
+            TryParseResult::Rejected(String::from("1234567890. foo"))
+        );
+
+        assert_eq!(
+            ListMarker::try_parse(String::from("    - foo"), &document),
+            TryParseResult::Rejected(String::from("    - foo"))
+        );
+    }
+
+    #[test]
+    fn it_indents_content_by_one_space_when_it_starts_with_indented_code() {
+        let document = Document::new();
+
+        assert_eq!(
+            ListMarker::try_parse(String::from("1.     code"), &document),
+            TryParseResult::Accepted(ParseResult(
+                (ListCategory::Ordered(1, '.'), 3),
+                Some(String::from("    code"))
+            ))
+        );
+    }
+
+    #[test]
+    fn it_advances_tabs_after_marker_to_tab_stops() {
+        let document = Document::new();
+
+        assert_eq!(
+            ListMarker::try_parse(String::from("-\tfoo"), &document),
+            TryParseResult::Accepted(ParseResult(
+                (ListCategory::Bullet('-'), 4),
+                Some(String::from("foo"))
+            ))
+        );
+
+        assert_eq!(
+            ListMarker::try_parse(String::from("-\t\tfoo"), &document),
+            TryParseResult::Accepted(ParseResult(
+                (ListCategory::Bullet('-'), 2),
+                Some(String::from("      foo"))
+            ))
+        );
+    }
+
+    #[test]
+    fn it_only_interrupts_paragraphs_with_non_empty_items_starting_at_one() {
+        let document = Document::new();
+
+        assert!(ListMarker::can_interrupt_paragraph(
+            String::from("- foo"),
+            &document
+        ));
+        assert!(ListMarker::can_interrupt_paragraph(
+            String::from("1. foo"),
+            &document
+        ));
+        assert!(!ListMarker::can_interrupt_paragraph(
+            String::from("2. foo"),
+            &document
+        ));
+        assert!(!ListMarker::can_interrupt_paragraph(
+            String::from("-\n"),
+            &document
+        ));
+    }
+}
@@ -1,33 +1,17 @@
-mod block_quote;
-
-use crate::document::parsers::ParseMultiple;
+pub mod block_quote;
+pub mod list;
+pub mod list_item;
 
 use super::{
-    Block, Document, Leaf, LeafCategory, Matcher, Parse, ParseResult, SelectionMatcher, TryParse,
-    TryParseResult,
+    Block, Document, IndividualMatcher, Matcher, ParseResult, TryParse, TryParseResult,
+    CODE_BLOCK_INDENTATION,
 };
-use block_quote::BlockQuote;
+use list::ListSpacing;
+use list_item::ListCategory;
 
+#[derive(Debug, Eq, PartialEq)]
 pub enum Container {
     BlockQuote(Vec<Block>),
-    // List(Vec<Block>),
-    // ListItem(Vec<Block>),
-}
-
-impl TryParse<Container> for Container {
-    fn try_parse(input: String, document: &Document) -> TryParseResult<Container> {
-        match BlockQuote::try_parse(input, document) {
-            TryParseResult::Accepted(ParseResult(consumed, unconsumed)) => {
-                TryParseResult::Accepted(ParseResult(
-                    Container::BlockQuote(Block::parse_multiple(consumed, document)),
-                    unconsumed,
-                ))
-            }
-            TryParseResult::Rejected(rejected) => {
-                // TODO
-
-                TryParseResult::Rejected(rejected)
-            }
-        }
-    }
+    List(ListCategory, ListSpacing, Vec<Block>),
+    ListItem(Vec<Block>),
 }
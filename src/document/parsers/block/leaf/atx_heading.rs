@@ -1,9 +1,9 @@
-use super::{
-    Document, IndividualMatcher, Matcher, ParseResult, SelectionMatcher, TryParse, TryParseResult,
-};
+use super::{Document, IndividualMatcher, Matcher, ParseResult, TryParse, TryParseResult};
+use crate::document::preliminaries::{is_line_ending, is_space_or_tab};
 
-#[derive(Debug, Eq, PartialEq)]
+pub const ATX_HEADING_DELIMITER: char = '#';
 
+#[derive(Debug, Eq, PartialEq)]
 pub enum AtxHeadingLevel {
     One,
     Two,
@@ -12,14 +12,272 @@ pub enum AtxHeadingLevel {
     Five,
     Six,
 }
-pub struct AtxHeading {
-    level: AtxHeadingLevel,
+
+impl AtxHeadingLevel {
+    fn from_delimiter_count(delimiter_count: usize) -> Option<AtxHeadingLevel> {
+        match delimiter_count {
+            1 => Some(AtxHeadingLevel::One),
+            2 => Some(AtxHeadingLevel::Two),
+            3 => Some(AtxHeadingLevel::Three),
+            4 => Some(AtxHeadingLevel::Four),
+            5 => Some(AtxHeadingLevel::Five),
+            6 => Some(AtxHeadingLevel::Six),
+            _ => None,
+        }
+    }
+}
+
+// [SPEC]: https://spec.commonmark.org/0.30/#atx-headings
+pub struct AtxHeading;
+
+impl AtxHeading {
+    // The optional closing sequence only counts as such if it is preceded by a space or a tab,
+    // or if it makes up the whole content (e.g. `### ###`).
+    fn strip_closing_sequence(content: &str) -> &str {
+        let without_closing_sequence = content.trim_end_matches(ATX_HEADING_DELIMITER);
+
+        if without_closing_sequence.is_empty()
+            || without_closing_sequence.ends_with(is_space_or_tab)
+        {
+            without_closing_sequence.trim_end_matches(is_space_or_tab)
+        } else {
+            content
+        }
+    }
 }
 
 impl TryParse<(AtxHeadingLevel, String)> for AtxHeading {
     fn try_parse(input: String, document: &Document) -> TryParseResult<(AtxHeadingLevel, String)> {
-        // TODO
+        let delimiter_matcher = IndividualMatcher::from(ATX_HEADING_DELIMITER);
+
+        let mut consumed = String::new();
+        let mut unconsumed = input;
+
+        let mut leading_space_count = 0;
+        let mut delimiter_count = 0;
+
+        while leading_space_count < 3 {
+            match document.preliminaries.space.try_match(unconsumed) {
+                Ok((matched_space, leftover_from_matched_space)) => {
+                    leading_space_count += 1;
+                    consumed = [consumed, matched_space].concat();
+                    unconsumed = leftover_from_matched_space;
+                }
+                Err(rejected_from_space_matcher) => {
+                    unconsumed = rejected_from_space_matcher;
+                    break;
+                }
+            }
+        }
+
+        loop {
+            match delimiter_matcher.try_match(unconsumed) {
+                Ok((matched_delimiter, leftover_from_matched_delimiter)) => {
+                    delimiter_count += 1;
+                    consumed = [consumed, matched_delimiter].concat();
+                    unconsumed = leftover_from_matched_delimiter;
+                }
+                Err(rejected_from_delimiter_matcher) => {
+                    unconsumed = rejected_from_delimiter_matcher;
+                    break;
+                }
+            }
+        }
+
+        let level = match AtxHeadingLevel::from_delimiter_count(delimiter_count) {
+            Some(level) => level,
+            None => return TryParseResult::Rejected([consumed, unconsumed].concat()),
+        };
+
+        let (matched_line, leftover_from_line_matcher) =
+            match document.preliminaries.line.try_match(unconsumed) {
+                Ok(matched) => matched,
+                Err(rejected_from_line_matcher) => (String::new(), rejected_from_line_matcher),
+            };
+
+        let content = matched_line.trim_end_matches(is_line_ending);
+
+        // The opening sequence has to be followed by a space, a tab or the end of the line, so
+        // that e.g. `#5 bolt` and `#hashtag` remain paragraphs.
+        if !content.is_empty() && !content.starts_with(is_space_or_tab) {
+            return TryParseResult::Rejected(
+                [consumed, matched_line, leftover_from_line_matcher].concat(),
+            );
+        }
+
+        let content = AtxHeading::strip_closing_sequence(content.trim_matches(is_space_or_tab));
+
+        TryParseResult::Accepted(ParseResult::new(
+            (level, String::from(content)),
+            leftover_from_line_matcher,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AtxHeading, AtxHeadingLevel, Document, ParseResult, TryParse, TryParseResult};
+
+    #[test]
+    fn it_accepts_all_six_levels() {
+        let document = Document::new();
+
+        assert_eq!(
+            AtxHeading::try_parse(String::from("# foo"), &document),
+            TryParseResult::Accepted(ParseResult(
+                (AtxHeadingLevel::One, String::from("foo")),
+                None
+            ))
+        );
+
+        assert_eq!(
+            AtxHeading::try_parse(String::from("###### foo"), &document),
+            TryParseResult::Accepted(ParseResult(
+                (AtxHeadingLevel::Six, String::from("foo")),
+                None
+            ))
+        );
+    }
+
+    #[test]
+    fn it_rejects_more_than_six_delimiters() {
+        let document = Document::new();
+
+        assert_eq!(
+            AtxHeading::try_parse(String::from("####### foo"), &document),
+            TryParseResult::Rejected(String::from("####### foo"))
+        );
+    }
+
+    #[test]
+    fn it_rejects_missing_space_after_opening_sequence() {
+        let document = Document::new();
+
+        assert_eq!(
+            AtxHeading::try_parse(String::from("#5 bolt"), &document),
+            TryParseResult::Rejected(String::from("#5 bolt"))
+        );
+
+        assert_eq!(
+            AtxHeading::try_parse(String::from("#hashtag"), &document),
+            TryParseResult::Rejected(String::from("#hashtag"))
+        );
+    }
+
+    #[test]
+    fn it_accepts_basic_example_and_returns_remaining_lines() {
+        let document = Document::new();
+
+        assert_eq!(
+            AtxHeading::try_parse(String::from("## foo\nleftover"), &document),
+            TryParseResult::Accepted(ParseResult(
+                (AtxHeadingLevel::Two, String::from("foo")),
+                Some(String::from("leftover"))
+            ))
+        );
+    }
+
+    #[test]
+    fn it_strips_leading_and_trailing_spaces_and_tabs() {
+        let document = Document::new();
+
+        assert_eq!(
+            AtxHeading::try_parse(String::from("#                  foo     \t\n"), &document),
+            TryParseResult::Accepted(ParseResult(
+                (AtxHeadingLevel::One, String::from("foo")),
+                None
+            ))
+        );
+    }
+
+    #[test]
+    fn it_accepts_up_to_three_spaces_of_indentation() {
+        let document = Document::new();
+
+        assert_eq!(
+            AtxHeading::try_parse(String::from("   ### foo"), &document),
+            TryParseResult::Accepted(ParseResult(
+                (AtxHeadingLevel::Three, String::from("foo")),
+                None
+            ))
+        );
+
+        assert_eq!(
+            AtxHeading::try_parse(String::from("    # foo"), &document),
+            TryParseResult::Rejected(String::from("    # foo"))
+        );
+    }
+
+    #[test]
+    fn it_strips_optional_closing_sequence() {
+        let document = Document::new();
+
+        assert_eq!(
+            AtxHeading::try_parse(String::from("## foo ##"), &document),
+            TryParseResult::Accepted(ParseResult(
+                (AtxHeadingLevel::Two, String::from("foo")),
+                None
+            ))
+        );
+
+        assert_eq!(
+            AtxHeading::try_parse(
+                String::from("# foo ##################################"),
+                &document
+            ),
+            TryParseResult::Accepted(ParseResult(
+                (AtxHeadingLevel::One, String::from("foo")),
+                None
+            ))
+        );
+
+        assert_eq!(
+            AtxHeading::try_parse(String::from("### foo ###     "), &document),
+            TryParseResult::Accepted(ParseResult(
+                (AtxHeadingLevel::Three, String::from("foo")),
+                None
+            ))
+        );
+    }
+
+    #[test]
+    fn it_keeps_closing_sequence_not_preceded_by_space() {
+        let document = Document::new();
+
+        assert_eq!(
+            AtxHeading::try_parse(String::from("# foo#"), &document),
+            TryParseResult::Accepted(ParseResult(
+                (AtxHeadingLevel::One, String::from("foo#")),
+                None
+            ))
+        );
+
+        assert_eq!(
+            AtxHeading::try_parse(String::from("### foo ### b"), &document),
+            TryParseResult::Accepted(ParseResult(
+                (AtxHeadingLevel::Three, String::from("foo ### b")),
+                None
+            ))
+        );
+    }
+
+    #[test]
+    fn it_accepts_empty_headings() {
+        let document = Document::new();
+
+        assert_eq!(
+            AtxHeading::try_parse(String::from("## \n"), &document),
+            TryParseResult::Accepted(ParseResult((AtxHeadingLevel::Two, String::new()), None))
+        );
+
+        assert_eq!(
+            AtxHeading::try_parse(String::from("#"), &document),
+            TryParseResult::Accepted(ParseResult((AtxHeadingLevel::One, String::new()), None))
+        );
 
-        TryParseResult::Rejected(input)
+        assert_eq!(
+            AtxHeading::try_parse(String::from("### ###"), &document),
+            TryParseResult::Accepted(ParseResult((AtxHeadingLevel::Three, String::new()), None))
+        );
     }
 }
@@ -0,0 +1,271 @@
+use super::{
+    unescape, Document, IndividualMatcher, Matcher, ParseResult, TryParse, TryParseResult,
+};
+use crate::document::preliminaries::{is_line_ending, is_space_or_tab, SPACE};
+
+const BACKTICK_FENCE_DELIMITER: char = '`';
+const TILDE_FENCE_DELIMITER: char = '~';
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct OpeningFence {
+    delimiter: char,
+    length: usize,
+    // Content lines lose as many columns of indentation as the opening fence was indented by,
+    // if they have that many.
+    pub indentation: usize,
+    pub info_string: Option<String>,
+}
+
+impl OpeningFence {
+    pub fn is_closed_by(&self, line: &str) -> bool {
+        let line = line.trim_end_matches(is_line_ending);
+        let unindented = line.trim_start_matches(SPACE);
+
+        if line.len() - unindented.len() > 3 {
+            return false;
+        }
+
+        let after_fence = unindented.trim_start_matches(self.delimiter);
+        let fence_length = unindented.len() - after_fence.len();
+
+        fence_length >= self.length && after_fence.chars().all(is_space_or_tab)
+    }
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum FencedCodeBlockStart {
+    // The content lines that follow belong to the code block, up to its closing fence.
+    Open(OpeningFence),
+    // Unlike CommonMark, which would treat this line as a code span, rustdown accepts a code
+    // block closed on its own opening line, e.g. ```` ```let x = 1;``` ````.
+    Closed(String),
+}
+
+// [SPEC]: https://spec.commonmark.org/0.30/#fenced-code-blocks
+pub struct FencedCodeBlock;
+
+// Accepts the opening line of a fenced code block.
+impl TryParse<FencedCodeBlockStart> for FencedCodeBlock {
+    fn try_parse(input: String, document: &Document) -> TryParseResult<FencedCodeBlockStart> {
+        let mut consumed = String::new();
+        let mut unconsumed = input;
+
+        let mut indentation = 0;
+
+        while indentation < 3 {
+            match document.preliminaries.space.try_match(unconsumed) {
+                Ok((matched_space, leftover_from_matched_space)) => {
+                    indentation += 1;
+                    consumed = [consumed, matched_space].concat();
+                    unconsumed = leftover_from_matched_space;
+                }
+                Err(rejected_from_space_matcher) => {
+                    unconsumed = rejected_from_space_matcher;
+                    break;
+                }
+            }
+        }
+
+        let delimiter = if unconsumed.starts_with(BACKTICK_FENCE_DELIMITER) {
+            BACKTICK_FENCE_DELIMITER
+        } else {
+            TILDE_FENCE_DELIMITER
+        };
+        let delimiter_matcher = IndividualMatcher::from(delimiter);
+
+        let mut opening_fence_length = 0;
+
+        loop {
+            match delimiter_matcher.try_match(unconsumed) {
+                Ok((matched_delimiter, leftover_from_matched_delimiter)) => {
+                    opening_fence_length += 1;
+                    consumed = [consumed, matched_delimiter].concat();
+                    unconsumed = leftover_from_matched_delimiter;
+                }
+                Err(rejected_from_delimiter_matcher) => {
+                    unconsumed = rejected_from_delimiter_matcher;
+                    break;
+                }
+            }
+        }
+
+        if opening_fence_length < 3 {
+            return TryParseResult::Rejected([consumed, unconsumed].concat());
+        }
+
+        let (matched_line, leftover_from_line_matcher) =
+            match document.preliminaries.line.try_match(unconsumed) {
+                Ok(matched) => matched,
+                Err(rejected_from_line_matcher) => (String::new(), rejected_from_line_matcher),
+            };
+
+        let info_string = matched_line
+            .trim_end_matches(is_line_ending)
+            .trim_matches(is_space_or_tab);
+
+        if delimiter == BACKTICK_FENCE_DELIMITER && info_string.contains(BACKTICK_FENCE_DELIMITER) {
+            let content = info_string.trim_end_matches(BACKTICK_FENCE_DELIMITER);
+            let closing_fence_length = info_string.len() - content.len();
+
+            if closing_fence_length == opening_fence_length
+                && !content.contains(BACKTICK_FENCE_DELIMITER)
+                && !content.trim_matches(is_space_or_tab).is_empty()
+            {
+                return TryParseResult::Accepted(ParseResult::new(
+                    FencedCodeBlockStart::Closed(String::from(content)),
+                    leftover_from_line_matcher,
+                ));
+            }
+
+            return TryParseResult::Rejected(
+                [consumed, matched_line, leftover_from_line_matcher].concat(),
+            );
+        }
+
+        let optional_info_string = match info_string.is_empty() {
+            false => Some(unescape(info_string, document)),
+            true => None,
+        };
+
+        TryParseResult::Accepted(ParseResult::new(
+            FencedCodeBlockStart::Open(OpeningFence {
+                delimiter,
+                length: opening_fence_length,
+                indentation,
+                info_string: optional_info_string,
+            }),
+            leftover_from_line_matcher,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Document, FencedCodeBlock, FencedCodeBlockStart, OpeningFence, ParseResult, TryParse,
+        TryParseResult,
+    };
+
+    fn opening_fence(
+        delimiter: char,
+        length: usize,
+        indentation: usize,
+        info_string: Option<&str>,
+    ) -> FencedCodeBlockStart {
+        FencedCodeBlockStart::Open(OpeningFence {
+            delimiter,
+            length,
+            indentation,
+            info_string: info_string.map(String::from),
+        })
+    }
+
+    #[test]
+    fn it_accepts_backtick_and_tilde_fences() {
+        let document = Document::new();
+
+        assert_eq!(
+            FencedCodeBlock::try_parse(String::from("```\n<\n"), &document),
+            TryParseResult::Accepted(ParseResult(
+                opening_fence('`', 3, 0, None),
+                Some(String::from("<\n"))
+            ))
+        );
+
+        assert_eq!(
+            FencedCodeBlock::try_parse(String::from("  ~~~~"), &document),
+            TryParseResult::Accepted(ParseResult(opening_fence('~', 4, 2, None), None))
+        );
+    }
+
+    #[test]
+    fn it_rejects_fewer_than_three_delimiters() {
+        let document = Document::new();
+
+        assert_eq!(
+            FencedCodeBlock::try_parse(String::from("``\nfoo\n``"), &document),
+            TryParseResult::Rejected(String::from("``\nfoo\n``"))
+        );
+    }
+
+    #[test]
+    fn it_requires_closing_fence_of_same_delimiter_and_at_least_same_length() {
+        let fence = OpeningFence {
+            delimiter: '`',
+            length: 4,
+            indentation: 0,
+            info_string: None,
+        };
+
+        assert!(fence.is_closed_by("````\n"));
+        assert!(fence.is_closed_by("   ``````  "));
+        assert!(!fence.is_closed_by("```"));
+        assert!(!fence.is_closed_by("~~~~"));
+        assert!(!fence.is_closed_by("    ````"));
+        assert!(!fence.is_closed_by("```` aaa"));
+    }
+
+    #[test]
+    fn it_rejects_four_spaces_of_indentation() {
+        let document = Document::new();
+
+        assert_eq!(
+            FencedCodeBlock::try_parse(String::from("    ```"), &document),
+            TryParseResult::Rejected(String::from("    ```"))
+        );
+    }
+
+    #[test]
+    fn it_parses_info_string() {
+        let document = Document::new();
+
+        assert_eq!(
+            FencedCodeBlock::try_parse(String::from("```   ruby startline=3 \n"), &document),
+            TryParseResult::Accepted(ParseResult(
+                opening_fence('`', 3, 0, Some("ruby startline=3")),
+                None
+            ))
+        );
+
+        assert_eq!(
+            FencedCodeBlock::try_parse(String::from("~~~ aa ``` ~~~"), &document),
+            TryParseResult::Accepted(ParseResult(
+                opening_fence('~', 3, 0, Some("aa ``` ~~~")),
+                None
+            ))
+        );
+    }
+
+    #[test]
+    fn it_rejects_backticks_in_backtick_fence_info_string() {
+        let document = Document::new();
+
+        assert_eq!(
+            FencedCodeBlock::try_parse(String::from("``` aa `` b\nfoo"), &document),
+            TryParseResult::Rejected(String::from("``` aa `` b\nfoo"))
+        );
+    }
+
+    #[test]
+    fn it_accepts_code_block_closed_on_opening_line() {
+        let document = Document::new();
+
+        assert_eq!(
+            FencedCodeBlock::try_parse(String::from("```let x = 1;```\nleftover"), &document),
+            TryParseResult::Accepted(ParseResult(
+                FencedCodeBlockStart::Closed(String::from("let x = 1;")),
+                Some(String::from("leftover"))
+            ))
+        );
+    }
+
+    #[test]
+    fn it_decodes_info_string() {
+        let document = Document::new();
+
+        assert_eq!(
+            FencedCodeBlock::try_parse(String::from("``` f&ouml;\\?"), &document),
+            TryParseResult::Accepted(ParseResult(opening_fence('`', 3, 0, Some("fö?")), None))
+        );
+    }
+}
@@ -0,0 +1,269 @@
+use super::{Document, HtmlTag, ParseResult, TryParse, TryParseResult};
+use crate::document::preliminaries::{is_line_ending, is_space_or_tab, SPACE};
+
+// Start condition 1.
+const RAW_TEXT_TAG_NAMES: [&str; 4] = ["pre", "script", "style", "textarea"];
+const RAW_TEXT_END_MARKERS: [&str; 4] = ["</pre>", "</script>", "</style>", "</textarea>"];
+
+// Start conditions 2 to 5.
+const COMMENT: (&str, &str) = ("<!--", "-->");
+const PROCESSING_INSTRUCTION: (&str, &str) = ("<?", "?>");
+const CDATA_SECTION: (&str, &str) = ("<![CDATA[", "]]>");
+const DECLARATION: (&str, &str) = ("<!", ">");
+
+// Start condition 6.
+const BLOCK_TAG_NAMES: [&str; 62] = [
+    "address",
+    "article",
+    "aside",
+    "base",
+    "basefont",
+    "blockquote",
+    "body",
+    "caption",
+    "center",
+    "col",
+    "colgroup",
+    "dd",
+    "details",
+    "dialog",
+    "dir",
+    "div",
+    "dl",
+    "dt",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "frame",
+    "frameset",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "head",
+    "header",
+    "hr",
+    "html",
+    "iframe",
+    "legend",
+    "li",
+    "link",
+    "main",
+    "menu",
+    "menuitem",
+    "nav",
+    "noframes",
+    "ol",
+    "optgroup",
+    "option",
+    "p",
+    "param",
+    "section",
+    "source",
+    "summary",
+    "table",
+    "tbody",
+    "td",
+    "tfoot",
+    "th",
+    "thead",
+    "title",
+    "tr",
+    "track",
+    "ul",
+];
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum HtmlBlockEnd {
+    // The block ends with the first line containing any of these (case-insensitively), which
+    // may be the line it started on.
+    Markers(&'static [&'static str]),
+    // The block ends right before the next blank line.
+    BlankLine,
+}
+
+impl HtmlBlockEnd {
+    pub fn is_met_by(&self, line: &str) -> bool {
+        match self {
+            HtmlBlockEnd::Markers(markers) => {
+                let lowercase = line.to_ascii_lowercase();
+
+                markers.iter().any(|marker| lowercase.contains(marker))
+            }
+            HtmlBlockEnd::BlankLine => false,
+        }
+    }
+}
+
+// [SPEC]: https://spec.commonmark.org/0.30/#html-blocks
+pub struct HtmlBlock;
+
+impl HtmlBlock {
+    // Returns how the HTML block started by this line ends, and whether it can interrupt a
+    // paragraph, or `None` if the line doesn't start an HTML block.
+    pub fn start_condition(line: &str, document: &Document) -> Option<(HtmlBlockEnd, bool)> {
+        let line = line.trim_end_matches(is_line_ending);
+        let unindented = line.trim_start_matches(SPACE);
+
+        if line.len() - unindented.len() > 3 {
+            return None;
+        }
+
+        let lowercase = unindented.to_ascii_lowercase();
+
+        let is_followed_by_tag_end = |rest: &str, allows_self_closing: bool| {
+            rest.is_empty()
+                || rest.starts_with(is_space_or_tab)
+                || rest.starts_with('>')
+                || (allows_self_closing && rest.starts_with("/>"))
+        };
+
+        if RAW_TEXT_TAG_NAMES.iter().any(|name| {
+            lowercase
+                .strip_prefix('<')
+                .and_then(|rest| rest.strip_prefix(name))
+                .is_some_and(|rest| is_followed_by_tag_end(rest, false))
+        }) {
+            return Some((HtmlBlockEnd::Markers(&RAW_TEXT_END_MARKERS), true));
+        }
+
+        if lowercase.starts_with(COMMENT.0) {
+            return Some((HtmlBlockEnd::Markers(&[COMMENT.1]), true));
+        }
+
+        if lowercase.starts_with(PROCESSING_INSTRUCTION.0) {
+            return Some((HtmlBlockEnd::Markers(&[PROCESSING_INSTRUCTION.1]), true));
+        }
+
+        if unindented.starts_with(CDATA_SECTION.0) {
+            return Some((HtmlBlockEnd::Markers(&[CDATA_SECTION.1]), true));
+        }
+
+        if lowercase
+            .strip_prefix(DECLARATION.0)
+            .is_some_and(|rest| rest.starts_with(|character: char| character.is_ascii_alphabetic()))
+        {
+            return Some((HtmlBlockEnd::Markers(&[DECLARATION.1]), true));
+        }
+
+        if let Some(rest) = lowercase
+            .strip_prefix("</")
+            .or_else(|| lowercase.strip_prefix('<'))
+        {
+            let name_length = rest
+                .find(|character: char| !character.is_ascii_alphanumeric())
+                .unwrap_or(rest.len());
+
+            if BLOCK_TAG_NAMES.contains(&&rest[..name_length])
+                && is_followed_by_tag_end(&rest[name_length..], true)
+            {
+                return Some((HtmlBlockEnd::BlankLine, true));
+            }
+        }
+
+        // Any other complete tag has to be alone on its line.
+        if let TryParseResult::Accepted(ParseResult(tag, optional_leftover)) =
+            HtmlTag::try_parse(String::from(unindented), document)
+        {
+            if !RAW_TEXT_TAG_NAMES.contains(&HtmlTag::name(&tag).as_str())
+                && optional_leftover
+                    .unwrap_or_default()
+                    .chars()
+                    .all(is_space_or_tab)
+            {
+                return Some((HtmlBlockEnd::BlankLine, false));
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Document, HtmlBlock, HtmlBlockEnd};
+
+    #[test]
+    fn it_ends_raw_text_block_at_closing_tag() {
+        let document = Document::new();
+
+        let (end, _) =
+            HtmlBlock::start_condition("<pre language=\"haskell\"><code>\n", &document).unwrap();
+
+        assert!(!end.is_met_by("main = print\n"));
+        assert!(end.is_met_by("</code></PRE>\n"));
+    }
+
+    #[test]
+    fn it_ends_comments_processing_instructions_declarations_and_cdata_at_their_markers() {
+        let document = Document::new();
+
+        for (start, last_line) in [
+            ("<!-- foo\n", "bar -->\n"),
+            ("<?php\n", "?>\n"),
+            ("<!DOCTYPE html\n", ">\n"),
+            ("<![CDATA[\n", "]]>\n"),
+        ] {
+            let (end, _) = HtmlBlock::start_condition(start, &document).unwrap();
+
+            assert!(!end.is_met_by("foo\n"));
+            assert!(end.is_met_by(last_line));
+        }
+    }
+
+    #[test]
+    fn it_ends_block_tag_block_before_blank_line() {
+        let document = Document::new();
+
+        assert_eq!(
+            HtmlBlock::start_condition("  <DETAILS open>\n", &document),
+            Some((HtmlBlockEnd::BlankLine, true))
+        );
+    }
+
+    #[test]
+    fn it_accepts_any_complete_tag_alone_on_its_line() {
+        let document = Document::new();
+
+        assert_eq!(
+            HtmlBlock::start_condition("<del>  \n", &document),
+            Some((HtmlBlockEnd::BlankLine, false))
+        );
+
+        assert_eq!(
+            HtmlBlock::start_condition("<del>*foo*</del>", &document),
+            None
+        );
+    }
+
+    #[test]
+    fn it_rejects_four_spaces_of_indentation_and_unknown_starts() {
+        let document = Document::new();
+
+        for line in ["    <div>", "<a href=\"foo\n", "< div>"] {
+            assert_eq!(HtmlBlock::start_condition(line, &document), None);
+        }
+    }
+
+    #[test]
+    fn it_only_lets_known_starts_interrupt_paragraphs() {
+        let document = Document::new();
+
+        assert!(matches!(
+            HtmlBlock::start_condition("<div>\n", &document),
+            Some((_, true))
+        ));
+        assert!(matches!(
+            HtmlBlock::start_condition("<!-- foo -->", &document),
+            Some((_, true))
+        ));
+        assert!(matches!(
+            HtmlBlock::start_condition("<a href=\"bar\">", &document),
+            Some((_, false))
+        ));
+    }
+}
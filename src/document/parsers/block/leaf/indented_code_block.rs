@@ -0,0 +1,53 @@
+use super::{is_line_ending, is_space_or_tab};
+
+// [SPEC]: https://spec.commonmark.org/0.30/#indented-code-blocks
+pub struct IndentedCodeBlock;
+
+impl IndentedCodeBlock {
+    // Blank lines are only part of the code block if more code follows them, so any at the end of
+    // its lines are dropped.
+    pub fn raw_content(lines: &str) -> String {
+        let mut content = String::new();
+        let mut pending_blank_lines = String::new();
+
+        for line in lines.split_inclusive(is_line_ending) {
+            if line
+                .chars()
+                .all(|character| is_space_or_tab(character) || is_line_ending(character))
+            {
+                pending_blank_lines.push_str(line);
+            } else {
+                content = [content, pending_blank_lines, String::from(line)].concat();
+                pending_blank_lines = String::new();
+            }
+        }
+
+        content
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IndentedCodeBlock;
+
+    #[test]
+    fn it_keeps_lines_as_they_are() {
+        assert_eq!(
+            IndentedCodeBlock::raw_content("a simple\n  indented code block"),
+            "a simple\n  indented code block"
+        );
+    }
+
+    #[test]
+    fn it_keeps_interior_blank_lines() {
+        assert_eq!(
+            IndentedCodeBlock::raw_content("chunk1\n\n\n  \nchunk2\n"),
+            "chunk1\n\n\n  \nchunk2\n"
+        );
+    }
+
+    #[test]
+    fn it_drops_trailing_blank_lines() {
+        assert_eq!(IndentedCodeBlock::raw_content("foo\n\n  \n"), "foo\n");
+    }
+}
@@ -0,0 +1,304 @@
+use super::{
+    Document, Leaf, LeafCategory, LinkDestination, LinkLabel, LinkTitle, ParseResult, TryParse,
+    TryParseResult,
+};
+use crate::document::{
+    parsers::utils::skip_whitespace,
+    preliminaries::{is_line_ending, is_space_or_tab, SPACE},
+};
+
+const LINK_LABEL_SEPARATOR: char = ':';
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct LinkReference {
+    pub destination: String,
+    pub title: Option<String>,
+}
+
+// [SPEC]: https://spec.commonmark.org/0.30/#link-reference-definitions
+pub struct LinkReferenceDefinition;
+
+impl LinkReferenceDefinition {
+    // Only spaces or tabs may follow a definition on its last line.
+    fn skip_line_end(text: &str) -> Option<&str> {
+        let after_spaces_or_tabs = text.trim_start_matches(is_space_or_tab);
+
+        if after_spaces_or_tabs.is_empty() {
+            return Some(after_spaces_or_tabs);
+        }
+
+        after_spaces_or_tabs
+            .strip_prefix("\r\n")
+            .or_else(|| after_spaces_or_tabs.strip_prefix(is_line_ending))
+    }
+
+    // A paragraph that consists only of definitions can't become a setext heading, since its
+    // content would be empty once the definitions are removed.
+    pub fn is_whole_content(text: String, document: &Document) -> bool {
+        let mut unconsumed = text;
+
+        loop {
+            unconsumed = match LinkReferenceDefinition::try_parse(unconsumed, document) {
+                TryParseResult::Accepted(ParseResult(_, optional_leftover)) => {
+                    optional_leftover.unwrap_or_default()
+                }
+                TryParseResult::Rejected(rejected_from_definition) => {
+                    return rejected_from_definition.is_empty()
+                }
+            };
+        }
+    }
+
+    // Definitions can only appear at the start of a paragraph (or of the content of a setext
+    // heading). They are removed from it, and the leaf is dropped if nothing else remains.
+    pub fn extract_from(
+        mut leaf: Leaf,
+        document: &Document,
+        definitions: &mut Vec<(String, LinkReference)>,
+    ) -> Option<Leaf> {
+        match leaf.category {
+            LeafCategory::Paragraph | LeafCategory::SetextHeading(_) => {
+                let mut unconsumed = leaf.text.clone();
+
+                while let TryParseResult::Accepted(ParseResult(definition, optional_leftover)) =
+                    LinkReferenceDefinition::try_parse(unconsumed.clone(), document)
+                {
+                    definitions.push(definition);
+                    unconsumed = optional_leftover.unwrap_or_default();
+                }
+
+                if unconsumed.is_empty() {
+                    None
+                } else {
+                    leaf.remove_text_prefix(leaf.text.len() - unconsumed.len(), document);
+
+                    Some(leaf)
+                }
+            }
+            _ => Some(leaf),
+        }
+    }
+}
+
+// Accepts a single definition, and returns its raw (not yet normalized) label alongside the
+// reference it defines.
+impl TryParse<(String, LinkReference)> for LinkReferenceDefinition {
+    fn try_parse(input: String, document: &Document) -> TryParseResult<(String, LinkReference)> {
+        let indentation = input.len() - input.trim_start_matches(SPACE).len();
+
+        if indentation > 3 {
+            return TryParseResult::Rejected(input);
+        }
+
+        let (label, leftover_from_label) =
+            match LinkLabel::try_parse(String::from(&input[indentation..]), document) {
+                TryParseResult::Accepted(ParseResult(label, optional_leftover)) => {
+                    (label, optional_leftover.unwrap_or_default())
+                }
+                TryParseResult::Rejected(_) => return TryParseResult::Rejected(input),
+            };
+
+        let after_separator = match leftover_from_label.strip_prefix(LINK_LABEL_SEPARATOR) {
+            Some(after_separator) => skip_whitespace(after_separator),
+            None => return TryParseResult::Rejected(input),
+        };
+
+        let (destination, leftover_from_destination) =
+            match LinkDestination::try_parse(String::from(after_separator), document) {
+                TryParseResult::Accepted(ParseResult(destination, optional_leftover)) => {
+                    (destination, optional_leftover.unwrap_or_default())
+                }
+                TryParseResult::Rejected(_) => return TryParseResult::Rejected(input),
+            };
+
+        let before_title = skip_whitespace(&leftover_from_destination);
+
+        // The title has to be separated from the destination by whitespace. If anything but
+        // whitespace follows it, the definition may still be valid without a title, as long as
+        // the title started on a new line.
+        if before_title.len() < leftover_from_destination.len() {
+            if let TryParseResult::Accepted(ParseResult(title, optional_leftover_from_title)) =
+                LinkTitle::try_parse(String::from(before_title), document)
+            {
+                let leftover_from_title = optional_leftover_from_title.unwrap_or_default();
+
+                if let Some(leftover) = LinkReferenceDefinition::skip_line_end(&leftover_from_title)
+                {
+                    return TryParseResult::Accepted(ParseResult::new(
+                        (
+                            label,
+                            LinkReference {
+                                destination,
+                                title: Some(title),
+                            },
+                        ),
+                        String::from(leftover),
+                    ));
+                }
+            }
+        }
+
+        match LinkReferenceDefinition::skip_line_end(&leftover_from_destination) {
+            Some(leftover) => TryParseResult::Accepted(ParseResult::new(
+                (
+                    label,
+                    LinkReference {
+                        destination,
+                        title: None,
+                    },
+                ),
+                String::from(leftover),
+            )),
+            None => TryParseResult::Rejected(input),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Document, Leaf, LeafCategory, LinkReference, LinkReferenceDefinition, ParseResult,
+        TryParse, TryParseResult,
+    };
+
+    #[test]
+    fn it_accepts_definition_with_title() {
+        let document = Document::new();
+
+        assert_eq!(
+            LinkReferenceDefinition::try_parse(
+                String::from("[foo]: /url \"title\"\nleftover"),
+                &document
+            ),
+            TryParseResult::Accepted(ParseResult(
+                (
+                    String::from("foo"),
+                    LinkReference {
+                        destination: String::from("/url"),
+                        title: Some(String::from("title")),
+                    }
+                ),
+                Some(String::from("leftover"))
+            ))
+        );
+    }
+
+    #[test]
+    fn it_accepts_definition_spanning_multiple_lines() {
+        let document = Document::new();
+
+        assert_eq!(
+            LinkReferenceDefinition::try_parse(
+                String::from("   [Foo bar]:\n<my url>\n'title'"),
+                &document
+            ),
+            TryParseResult::Accepted(ParseResult(
+                (
+                    String::from("Foo bar"),
+                    LinkReference {
+                        destination: String::from("my url"),
+                        title: Some(String::from("title")),
+                    }
+                ),
+                None
+            ))
+        );
+    }
+
+    #[test]
+    fn it_accepts_definition_without_title() {
+        let document = Document::new();
+
+        assert_eq!(
+            LinkReferenceDefinition::try_parse(String::from("[foo]:\n/url"), &document),
+            TryParseResult::Accepted(ParseResult(
+                (
+                    String::from("foo"),
+                    LinkReference {
+                        destination: String::from("/url"),
+                        title: None,
+                    }
+                ),
+                None
+            ))
+        );
+    }
+
+    #[test]
+    fn it_drops_title_followed_by_other_text_on_a_new_line() {
+        let document = Document::new();
+
+        assert_eq!(
+            LinkReferenceDefinition::try_parse(
+                String::from("[foo]: /url\n\"title\" ok"),
+                &document
+            ),
+            TryParseResult::Accepted(ParseResult(
+                (
+                    String::from("foo"),
+                    LinkReference {
+                        destination: String::from("/url"),
+                        title: None,
+                    }
+                ),
+                Some(String::from("\"title\" ok"))
+            ))
+        );
+    }
+
+    #[test]
+    fn it_rejects_other_text_after_title_on_the_same_line() {
+        let document = Document::new();
+
+        assert_eq!(
+            LinkReferenceDefinition::try_parse(String::from("[foo]: /url \"title\" ok"), &document),
+            TryParseResult::Rejected(String::from("[foo]: /url \"title\" ok"))
+        );
+    }
+
+    #[test]
+    fn it_rejects_missing_destination() {
+        let document = Document::new();
+
+        assert_eq!(
+            LinkReferenceDefinition::try_parse(String::from("[foo]:\n\n[foo]"), &document),
+            TryParseResult::Rejected(String::from("[foo]:\n\n[foo]"))
+        );
+    }
+
+    #[test]
+    fn it_extracts_definitions_from_start_of_paragraph() {
+        let document = Document::new();
+        let mut definitions = Vec::new();
+
+        let optional_leaf = LinkReferenceDefinition::extract_from(
+            Leaf::new(
+                LeafCategory::Paragraph,
+                String::from("[foo]: /foo\n[bar]: /bar\nbaz [qux]: /qux"),
+            ),
+            &document,
+            &mut definitions,
+        );
+
+        assert_eq!(definitions.len(), 2);
+        assert_eq!(
+            optional_leaf.map(|leaf| leaf.text),
+            Some(String::from("baz [qux]: /qux"))
+        );
+    }
+
+    #[test]
+    fn it_drops_paragraph_consisting_only_of_definitions() {
+        let document = Document::new();
+        let mut definitions = Vec::new();
+
+        let optional_leaf = LinkReferenceDefinition::extract_from(
+            Leaf::new(LeafCategory::Paragraph, String::from("[foo]: /foo\n")),
+            &document,
+            &mut definitions,
+        );
+
+        assert_eq!(definitions.len(), 1);
+        assert!(optional_leaf.is_none());
+    }
+}
@@ -1,67 +1,120 @@
 pub mod atx_heading;
-mod paragraph;
-mod thematic_break;
+pub mod fenced_code_block;
+pub mod html_block;
+pub mod indented_code_block;
+pub mod link_reference_definition;
+pub mod paragraph;
+pub mod setext_heading;
+pub mod thematic_break;
 
 use super::{
-    Document, IndividualMatcher, Matcher, Parse, ParseResult, SelectionMatcher, TryParse,
-    TryParseResult,
+    Document, IndividualMatcher, Matcher, ParseResult, SelectionMatcher, TryParse, TryParseResult,
 };
-use atx_heading::{AtxHeading, AtxHeadingLevel};
-use paragraph::Paragraph;
-use thematic_break::ThematicBreak;
+use crate::document::{
+    parsers::inline::Inline,
+    parsers::utils::{unescape, HtmlTag, LinkDestination, LinkLabel, LinkTitle},
+    preliminaries::{is_line_ending, is_space_or_tab, Span},
+};
+use atx_heading::AtxHeadingLevel;
+use setext_heading::SetextHeadingLevel;
 
-#[derive(Eq, PartialEq)]
+#[derive(Debug, Eq, PartialEq)]
 pub enum LeafCategory {
     AtxHeading(AtxHeadingLevel),
+    SetextHeading(SetextHeadingLevel),
     ThematicBreak,
+    IndentedCodeBlock,
+    FencedCodeBlock(Option<String>),
+    HtmlBlock,
     Paragraph,
 }
 
+// The text is the raw content of the leaf. Headings and paragraphs also hold the inlines parsed
+// from it, once the whole block structure is known.
+#[derive(Debug, Eq, PartialEq)]
 pub struct Leaf {
     pub category: LeafCategory,
     pub text: String,
+    pub inlines: Vec<Inline>,
+    pub span: Span,
+    // The lines of the text may come from anywhere in the input, with container markers and
+    // indentation in between, so the offset in the text where each one starts is paired with the
+    // offset in the normalized input it comes from.
+    content_offsets: Vec<(usize, usize)>,
 }
 
 impl Leaf {
-    fn new(category: LeafCategory, text: String) -> Leaf {
-        Leaf { category, text }
+    pub fn new(category: LeafCategory, text: String) -> Leaf {
+        Leaf {
+            category,
+            text,
+            inlines: Vec::new(),
+            span: Span::default(),
+            content_offsets: Vec::new(),
+        }
     }
-}
 
-impl Parse<Leaf> for Leaf {
-    fn parse(input: String, document: &Document) -> ParseResult<Leaf> {
-        // TODO
+    pub(crate) fn with_source(mut self, span: Span, content_offsets: Vec<(usize, usize)>) -> Leaf {
+        self.span = span;
+        self.content_offsets = content_offsets;
+        self
+    }
+
+    // [SPEC]: https://spec.commonmark.org/0.30/#inlines
+    pub fn has_inline_content(&self) -> bool {
+        matches!(
+            self.category,
+            LeafCategory::AtxHeading(_) | LeafCategory::SetextHeading(_) | LeafCategory::Paragraph
+        )
+    }
+
+    // Maps an offset in the text to the offset in the normalized input it comes from.
+    fn source_offset(&self, text_offset: usize) -> usize {
+        let line_count = self
+            .content_offsets
+            .partition_point(|(line_offset, _)| *line_offset <= text_offset);
+
+        match line_count {
+            0 => text_offset,
+            _ => {
+                let (line_offset, source_offset) = self.content_offsets[line_count - 1];
 
-        match AtxHeading::try_parse(input, document) {
-            TryParseResult::Accepted(ParseResult(
-                (atx_heading_level, matched_atx_heading),
-                leftover_from_atx_heading,
-            )) => ParseResult(
-                Leaf::new(
-                    LeafCategory::AtxHeading(atx_heading_level),
-                    matched_atx_heading,
-                ),
-                leftover_from_atx_heading,
-            ),
-            TryParseResult::Rejected(rejected_from_atx_heading_matcher) => {
-                match ThematicBreak::try_parse(rejected_from_atx_heading_matcher, document) {
-                    TryParseResult::Accepted(ParseResult(
-                        matched_thematic_break,
-                        leftover_from_thematic_break,
-                    )) => ParseResult(
-                        Leaf::new(LeafCategory::ThematicBreak, matched_thematic_break),
-                        leftover_from_thematic_break,
-                    ),
-                    TryParseResult::Rejected(rejected_from_thematic_break_matcher) => {
-                        let ParseResult(matched_paragraph, leftover_from_paragraph) =
-                            Paragraph::parse(rejected_from_thematic_break_matcher, document);
-                        ParseResult(
-                            Leaf::new(LeafCategory::Paragraph, matched_paragraph),
-                            leftover_from_paragraph,
-                        )
-                    }
-                }
+                source_offset + text_offset - line_offset
             }
         }
     }
+
+    // Removes the start of the text, such as link reference definitions, so that the leaf starts
+    // where the rest of the text does.
+    pub(crate) fn remove_text_prefix(&mut self, length: usize, document: &Document) {
+        let start = self.source_offset(length);
+
+        self.content_offsets = std::iter::once((0, start))
+            .chain(
+                self.content_offsets
+                    .iter()
+                    .filter(|(line_offset, _)| *line_offset > length)
+                    .map(|(line_offset, source_offset)| (line_offset - length, *source_offset)),
+            )
+            .collect();
+        self.text = self.text.split_off(length);
+        self.span.start = document.source_map.position(start);
+    }
+
+    // Inlines are parsed from the text, so their spans are mapped from offsets in the text to
+    // positions in the input.
+    pub(crate) fn locate_inlines(&mut self, document: &Document) {
+        let mut inlines = std::mem::take(&mut self.inlines);
+
+        for inline in &mut inlines {
+            inline.map_spans(&|span: Span| {
+                document.source_map.span(
+                    self.source_offset(span.start.offset),
+                    self.source_offset(span.end.offset),
+                )
+            });
+        }
+
+        self.inlines = inlines;
+    }
 }
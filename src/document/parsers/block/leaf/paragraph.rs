@@ -1,60 +1,35 @@
-use super::{Document, Matcher, Parse, ParseResult};
+use super::{is_line_ending, is_space_or_tab};
 
-#[derive(Debug, Eq, PartialEq)]
+// [SPEC]: https://spec.commonmark.org/0.30/#paragraphs
 pub struct Paragraph;
 
-impl Parse<String> for Paragraph {
-    fn parse(input: String, document: &Document) -> ParseResult<String> {
-        let mut consumed = String::new();
-        let mut unconsumed = input;
+impl Paragraph {
+    // The raw content has the spaces or tabs at the start of each line removed, as well as the
+    // whitespace and line ending at the end of the paragraph. Any other trailing spaces are left
+    // for inline parsing, where they may form a hard line break.
+    pub fn raw_content(lines: &str) -> String {
+        let content: String = lines
+            .split_inclusive(is_line_ending)
+            .map(|line| line.trim_start_matches(is_space_or_tab))
+            .collect();
 
-        while !unconsumed.is_empty() {
-            match document.preliminaries.blank_line.try_match(unconsumed) {
-                Ok((_, leftover_from_matched_blank_line)) => {
-                    unconsumed = leftover_from_matched_blank_line;
-                    break;
-                }
-                Err(rejected_from_blank_line_matcher) => {
-                    match document
-                        .preliminaries
-                        .line
-                        .try_match(rejected_from_blank_line_matcher)
-                    {
-                        Ok((matched_line, leftover_from_line_matcher)) => {
-                            consumed = [consumed, matched_line].concat();
-                            unconsumed = leftover_from_line_matcher;
-                            continue;
-                        }
-                        Err(rejected_from_line_matcher) => {
-                            unconsumed = rejected_from_line_matcher;
-                            break;
-                        }
-                    }
-                }
-            }
-        }
-
-        ParseResult::new(consumed, unconsumed)
+        String::from(
+            content.trim_end_matches(|character| {
+                is_space_or_tab(character) || is_line_ending(character)
+            }),
+        )
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{Document, Paragraph, Parse, ParseResult};
+    use super::Paragraph;
 
     #[test]
-    fn it_accepts_any_sequence_of_non_blank_lines() {
-        let document = Document::new();
-
+    fn it_strips_leading_whitespace_of_lines_and_trailing_whitespace_of_paragraph() {
         assert_eq!(
-            Paragraph::parse(
-                String::from("paragraph\ncontinued paragraph\n\nsomething else"),
-                &document
-            ),
-            ParseResult(
-                String::from("paragraph\ncontinued paragraph\n"),
-                Some(String::from("something else"))
-            )
+            Paragraph::raw_content("  aaa  \r\n\t bbb\\\n   ccc \t\n"),
+            "aaa  \r\nbbb\\\nccc"
         );
     }
 }
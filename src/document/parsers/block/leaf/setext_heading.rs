@@ -0,0 +1,117 @@
+use super::{Document, Matcher, ParseResult, TryParse, TryParseResult};
+use crate::document::preliminaries::{is_line_ending, is_space_or_tab};
+
+const LEVEL_ONE_DELIMITER: char = '=';
+const LEVEL_TWO_DELIMITER: char = '-';
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum SetextHeadingLevel {
+    One,
+    Two,
+}
+
+// [SPEC]: https://spec.commonmark.org/0.30/#setext-heading-underline
+pub struct SetextHeadingUnderline;
+
+impl TryParse<SetextHeadingLevel> for SetextHeadingUnderline {
+    fn try_parse(input: String, document: &Document) -> TryParseResult<SetextHeadingLevel> {
+        let mut consumed = String::new();
+        let mut unconsumed = input;
+
+        let mut leading_space_count = 0;
+
+        while leading_space_count < 3 {
+            match document.preliminaries.space.try_match(unconsumed) {
+                Ok((matched_space, leftover_from_matched_space)) => {
+                    leading_space_count += 1;
+                    consumed = [consumed, matched_space].concat();
+                    unconsumed = leftover_from_matched_space;
+                }
+                Err(rejected_from_space_matcher) => {
+                    unconsumed = rejected_from_space_matcher;
+                    break;
+                }
+            }
+        }
+
+        let (matched_line, leftover_from_line_matcher) =
+            match document.preliminaries.line.try_match(unconsumed) {
+                Ok(matched) => matched,
+                Err(rejected_from_line_matcher) => (String::new(), rejected_from_line_matcher),
+            };
+
+        let underline = matched_line
+            .trim_end_matches(is_line_ending)
+            .trim_end_matches(is_space_or_tab);
+
+        let optional_level = if underline.is_empty() {
+            None
+        } else if underline
+            .chars()
+            .all(|character| character == LEVEL_ONE_DELIMITER)
+        {
+            Some(SetextHeadingLevel::One)
+        } else if underline
+            .chars()
+            .all(|character| character == LEVEL_TWO_DELIMITER)
+        {
+            Some(SetextHeadingLevel::Two)
+        } else {
+            None
+        };
+
+        match optional_level {
+            Some(level) => {
+                TryParseResult::Accepted(ParseResult::new(level, leftover_from_line_matcher))
+            }
+            None => TryParseResult::Rejected(
+                [consumed, matched_line, leftover_from_line_matcher].concat(),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Document, ParseResult, SetextHeadingLevel, SetextHeadingUnderline, TryParse, TryParseResult,
+    };
+
+    #[test]
+    fn it_accepts_underline_of_any_length_with_indentation_and_trailing_spaces() {
+        let document = Document::new();
+
+        assert_eq!(
+            SetextHeadingUnderline::try_parse(String::from("="), &document),
+            TryParseResult::Accepted(ParseResult(SetextHeadingLevel::One, None))
+        );
+
+        assert_eq!(
+            SetextHeadingUnderline::try_parse(String::from("   ----      \nleftover"), &document),
+            TryParseResult::Accepted(ParseResult(
+                SetextHeadingLevel::Two,
+                Some(String::from("leftover"))
+            ))
+        );
+    }
+
+    #[test]
+    fn it_rejects_invalid_underlines() {
+        let document = Document::new();
+
+        assert_eq!(
+            SetextHeadingUnderline::try_parse(String::from("    ---"), &document),
+            TryParseResult::Rejected(String::from("    ---"))
+        );
+
+        assert_eq!(
+            SetextHeadingUnderline::try_parse(String::from("= ="), &document),
+            TryParseResult::Rejected(String::from("= ="))
+        );
+
+        assert_eq!(
+            SetextHeadingUnderline::try_parse(String::from("---="), &document),
+            TryParseResult::Rejected(String::from("---="))
+        );
+    }
+}
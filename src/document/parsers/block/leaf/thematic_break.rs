@@ -1,6 +1,7 @@
 use super::{
     Document, IndividualMatcher, Matcher, ParseResult, SelectionMatcher, TryParse, TryParseResult,
 };
+use crate::document::preliminaries::{SPACE, TAB};
 
 #[derive(Debug, Eq, PartialEq)]
 pub struct ThematicBreak;
@@ -12,6 +13,8 @@ impl TryParse<String> for ThematicBreak {
             String::from('_'),
             String::from('*'),
         ]);
+        let space_or_tab_matcher =
+            SelectionMatcher::from(vec![String::from(SPACE), String::from(TAB)]);
 
         let mut optional_delimiter_individual_matcher: Option<Box<dyn Matcher>> = None;
         let mut delimiter_count = 0;
@@ -36,7 +39,7 @@ impl TryParse<String> for ThematicBreak {
                                 IndividualMatcher::from(String::from(&matched_delimiter)),
                             ));
 
-                            delimiter_count = delimiter_count + 1;
+                            delimiter_count += 1;
                             consumed = [consumed, matched_delimiter].concat();
                             unconsumed = delimiter_leftover;
                             continue;
@@ -46,7 +49,7 @@ impl TryParse<String> for ThematicBreak {
                                 match document.preliminaries.space.try_match(rejected_delimiter) {
                                     Ok((matched_space, space_leftover)) => {
                                         consumed = [consumed, matched_space].concat();
-                                        leading_space_count = leading_space_count + 1;
+                                        leading_space_count += 1;
                                         unconsumed = space_leftover;
                                         continue;
                                     }
@@ -64,7 +67,7 @@ impl TryParse<String> for ThematicBreak {
                     Some(delimiter_individual_matcher) => {
                         match delimiter_individual_matcher.try_match(rejected_line_ending) {
                             Ok((delimiter_match_result, leftover_from_delimiter)) => {
-                                delimiter_count = delimiter_count + 1;
+                                delimiter_count += 1;
                                 consumed = [consumed, delimiter_match_result].concat();
                                 unconsumed = leftover_from_delimiter;
 
@@ -75,15 +78,15 @@ impl TryParse<String> for ThematicBreak {
                                 continue;
                             }
                             Err(rejected_delimiter) => {
-                                match document.preliminaries.space.try_match(rejected_delimiter) {
-                                    Ok((matched_space, space_leftover)) => {
-                                        consumed = [consumed, matched_space].concat();
-                                        unconsumed = space_leftover;
+                                match space_or_tab_matcher.try_match(rejected_delimiter) {
+                                    Ok((matched_space_or_tab, space_or_tab_leftover)) => {
+                                        consumed = [consumed, matched_space_or_tab].concat();
+                                        unconsumed = space_or_tab_leftover;
                                         continue;
                                     }
-                                    Err(rejected_space) => {
+                                    Err(rejected_space_or_tab) => {
                                         is_thematic_break = false;
-                                        unconsumed = rejected_space;
+                                        unconsumed = rejected_space_or_tab;
                                         break;
                                     }
                                 }
@@ -95,9 +98,9 @@ impl TryParse<String> for ThematicBreak {
         }
 
         if is_thematic_break {
-            return TryParseResult::Accepted(ParseResult::new(consumed, unconsumed));
+            TryParseResult::Accepted(ParseResult::new(consumed, unconsumed))
         } else {
-            return TryParseResult::Rejected([consumed, unconsumed].concat());
+            TryParseResult::Rejected([consumed, unconsumed].concat())
         }
     }
 }
@@ -253,4 +256,24 @@ mod tests {
             TryParseResult::Rejected(String::from("*-*"))
         );
     }
+
+    #[test]
+    fn it_accepts_tabs_between_delimiters() {
+        let document = Document::new();
+
+        assert_eq!(
+            ThematicBreak::try_parse(String::from("*\t*\t*\t"), &document),
+            TryParseResult::Accepted(ParseResult(String::from("*\t*\t*\t"), None))
+        );
+    }
+
+    #[test]
+    fn it_rejects_leading_tab() {
+        let document = Document::new();
+
+        assert_eq!(
+            ThematicBreak::try_parse(String::from("\t***"), &document),
+            TryParseResult::Rejected(String::from("\t***"))
+        );
+    }
 }
@@ -1,49 +1,158 @@
 pub mod container;
 pub mod leaf;
+mod open_blocks;
 
 use super::{
-    Document, IndividualMatcher, Matcher, Parse, ParseMultiple, ParseResult, SelectionMatcher,
-    TryParse, TryParseResult,
+    inline::Inline, Document, IndividualMatcher, Matcher, ParseMultiple, ParseResult,
+    SelectionMatcher, TryParse, TryParseResult,
 };
+use crate::document::preliminaries::Span;
 use container::Container;
-use leaf::{Leaf, LeafCategory};
+use leaf::{
+    link_reference_definition::{LinkReference, LinkReferenceDefinition},
+    Leaf,
+};
+use open_blocks::OpenBlocks;
+
+// Lines indented by at least this many spaces can only be part of an indented code block or
+// continue a paragraph.
+pub const CODE_BLOCK_INDENTATION: usize = 4;
 
+#[derive(Debug, Eq, PartialEq)]
 pub enum Block {
-    Container(Container),
+    Container(Container, Span),
     Leaf(Leaf),
 }
 
-impl Parse<Block> for Block {
-    fn parse(input: String, document: &Document) -> ParseResult<Block> {
-        match Container::try_parse(input, document) {
-            TryParseResult::Accepted(parse_result) => {
-                let ParseResult(container, unconsumed) = parse_result;
-                ParseResult(Block::Container(container), unconsumed)
-            }
-            TryParseResult::Rejected(rejected) => {
-                let ParseResult(leaf, unconsumed) = Leaf::parse(rejected, document);
-                ParseResult(Block::Leaf(leaf), unconsumed)
-            }
+impl Block {
+    pub fn span(&self) -> Span {
+        match self {
+            Block::Container(_, span) => *span,
+            Block::Leaf(leaf) => leaf.span,
         }
     }
+
+    // [SPEC]: https://spec.commonmark.org/0.30/#phase-2-inline-structure
+    // The second phase of parsing, which parses the raw content of headings and paragraphs into
+    // inlines. It comes after link reference definitions are extracted, so that links can refer
+    // to definitions anywhere in the document.
+    pub(crate) fn parse_inlines(blocks: Vec<Block>, document: &Document) -> Vec<Block> {
+        blocks
+            .into_iter()
+            .map(|block| match block {
+                Block::Container(container, span) => {
+                    let container = match container {
+                        Container::BlockQuote(child_blocks) => {
+                            Container::BlockQuote(Block::parse_inlines(child_blocks, document))
+                        }
+                        Container::List(category, spacing, items) => Container::List(
+                            category,
+                            spacing,
+                            Block::parse_inlines(items, document),
+                        ),
+                        Container::ListItem(child_blocks) => {
+                            Container::ListItem(Block::parse_inlines(child_blocks, document))
+                        }
+                    };
+
+                    Block::Container(container, span)
+                }
+                Block::Leaf(mut leaf) => {
+                    if leaf.has_inline_content() {
+                        leaf.inlines = Inline::parse_multiple(leaf.text.clone(), document);
+                        leaf.locate_inlines(document);
+                    }
+
+                    Block::Leaf(leaf)
+                }
+            })
+            .collect()
+    }
+
+    // Link reference definitions are removed from the block structure before rendering, since
+    // references may point to definitions found anywhere in the document. They are returned in
+    // document order.
+    pub(crate) fn extract_link_reference_definitions(
+        blocks: Vec<Block>,
+        document: &Document,
+    ) -> (Vec<Block>, Vec<(String, LinkReference)>) {
+        let mut definitions = Vec::new();
+        let blocks =
+            Block::extract_link_reference_definitions_into(blocks, document, &mut definitions);
+
+        (blocks, definitions)
+    }
+
+    fn extract_link_reference_definitions_into(
+        blocks: Vec<Block>,
+        document: &Document,
+        definitions: &mut Vec<(String, LinkReference)>,
+    ) -> Vec<Block> {
+        blocks
+            .into_iter()
+            .filter_map(|block| match block {
+                Block::Container(container, span) => {
+                    let container = match container {
+                        Container::BlockQuote(child_blocks) => {
+                            Container::BlockQuote(Block::extract_link_reference_definitions_into(
+                                child_blocks,
+                                document,
+                                definitions,
+                            ))
+                        }
+                        Container::List(category, spacing, items) => Container::List(
+                            category,
+                            spacing,
+                            Block::extract_link_reference_definitions_into(
+                                items,
+                                document,
+                                definitions,
+                            ),
+                        ),
+                        Container::ListItem(child_blocks) => {
+                            Container::ListItem(Block::extract_link_reference_definitions_into(
+                                child_blocks,
+                                document,
+                                definitions,
+                            ))
+                        }
+                    };
+
+                    Some(Block::Container(container, span))
+                }
+                Block::Leaf(leaf) => {
+                    LinkReferenceDefinition::extract_from(leaf, document, definitions)
+                        .map(Block::Leaf)
+                }
+            })
+            .collect()
+    }
 }
 
+// [SPEC]: https://spec.commonmark.org/0.30/#appendix-a-parsing-strategy
+// Builds the block structure by feeding the input to the open blocks one line at a time. The
+// inline content of the leaves is parsed separately, once the whole block structure is known.
 impl ParseMultiple<Block> for Block {
     fn parse_multiple(input: String, document: &Document) -> Vec<Block> {
-        let mut blocks = Vec::new();
-        let mut optional_unconsumed = Some(input);
+        let mut open_blocks = OpenBlocks::new(&document.source_map);
+        let input_length = input.len();
+        let mut unconsumed = input;
 
-        loop {
-            if let Some(unconsumed) = optional_unconsumed {
-                let ParseResult(block, optional_leftover) = Block::parse(unconsumed, document);
+        while !unconsumed.is_empty() {
+            let offset = input_length - unconsumed.len();
 
-                blocks.push(block);
-                optional_unconsumed = optional_leftover;
-            } else {
-                break;
+            match document.preliminaries.line.try_match(unconsumed) {
+                Ok((matched_line, leftover_from_line_matcher)) => {
+                    open_blocks.add_line(matched_line, offset, document);
+                    unconsumed = leftover_from_line_matcher;
+                }
+                Err(rejected_from_line_matcher) => {
+                    open_blocks.add_line(rejected_from_line_matcher, offset, document);
+                    break;
+                }
             }
         }
 
-        blocks
+        open_blocks.close_all()
     }
 }
@@ -0,0 +1,897 @@
+use super::{
+    container::{
+        block_quote::BlockQuote,
+        list::ListSpacing,
+        list_item::{ListCategory, ListMarker},
+        Container,
+    },
+    leaf::{
+        atx_heading::{AtxHeading, ATX_HEADING_DELIMITER},
+        fenced_code_block::{FencedCodeBlock, FencedCodeBlockStart, OpeningFence},
+        html_block::{HtmlBlock, HtmlBlockEnd},
+        indented_code_block::IndentedCodeBlock,
+        link_reference_definition::LinkReferenceDefinition,
+        paragraph::Paragraph,
+        setext_heading::SetextHeadingUnderline,
+        thematic_break::ThematicBreak,
+        Leaf, LeafCategory,
+    },
+    Block, Document, Matcher, ParseResult, TryParse, TryParseResult, CODE_BLOCK_INDENTATION,
+};
+use crate::document::preliminaries::{is_line_ending, is_space_or_tab, SourceMap, NEWLINE};
+
+enum OpenBlockCategory {
+    Document,
+    BlockQuote,
+    List(ListCategory),
+    // Holds the number of columns the content of the item is indented by.
+    ListItem(usize),
+    Paragraph,
+    IndentedCodeBlock,
+    FencedCodeBlock(OpeningFence),
+    HtmlBlock(HtmlBlockEnd),
+}
+
+struct OpenBlock {
+    category: OpenBlockCategory,
+    // Containers hold the child blocks closed so far, while the open child, if any, comes next
+    // on the stack.
+    children: Vec<Block>,
+    // Leaves hold the lines added to them, which become their content when they're closed.
+    lines: String,
+    // Offsets in the normalized input, which become the span of the block when it's closed.
+    start: usize,
+    end: usize,
+    // The offset in the lines where each line of a paragraph starts, paired with its offset in
+    // the normalized input.
+    content_offsets: Vec<(usize, usize)>,
+    last_line_blank: bool,
+    last_child_ends_with_blank_line: bool,
+    is_loose: bool,
+}
+
+impl OpenBlock {
+    fn new(category: OpenBlockCategory, start: usize, end: usize) -> OpenBlock {
+        OpenBlock {
+            category,
+            children: Vec::new(),
+            lines: String::new(),
+            start,
+            end,
+            content_offsets: Vec::new(),
+            last_line_blank: false,
+            last_child_ends_with_blank_line: false,
+            is_loose: false,
+        }
+    }
+
+    fn can_contain(&self, category: &OpenBlockCategory) -> bool {
+        match self.category {
+            OpenBlockCategory::Document
+            | OpenBlockCategory::BlockQuote
+            | OpenBlockCategory::ListItem(_) => !matches!(category, OpenBlockCategory::ListItem(_)),
+            OpenBlockCategory::List(_) => matches!(category, OpenBlockCategory::ListItem(_)),
+            _ => false,
+        }
+    }
+
+    fn is_list_or_list_item(&self) -> bool {
+        matches!(
+            self.category,
+            OpenBlockCategory::List(_) | OpenBlockCategory::ListItem(_)
+        )
+    }
+
+    // Blank lines are part of the content of these blocks, rather than separating blocks.
+    fn keeps_blank_lines(&self) -> bool {
+        matches!(
+            self.category,
+            OpenBlockCategory::FencedCodeBlock(_) | OpenBlockCategory::HtmlBlock(_)
+        )
+    }
+
+    // [SPEC]: https://spec.commonmark.org/0.30/#loose
+    // A list is loose if any of its items are separated by a blank line, or if any of its items
+    // directly contain two blocks with a blank line between them. So, a container that gets a
+    // new child after one that ended with a blank line is marked as loose.
+    fn add_child(&mut self, block: Block, ends_with_blank_line: bool) {
+        if !self.children.is_empty() && self.last_child_ends_with_blank_line {
+            self.is_loose = true;
+        }
+
+        self.last_child_ends_with_blank_line = ends_with_blank_line;
+        self.children.push(block);
+    }
+
+    // The document itself is never closed into a block.
+    fn into_block(self, source_map: &SourceMap) -> Option<Block> {
+        let span = source_map.span(self.start, self.end);
+
+        let block = match self.category {
+            OpenBlockCategory::Document => return None,
+            OpenBlockCategory::BlockQuote => {
+                Block::Container(Container::BlockQuote(self.children), span)
+            }
+            OpenBlockCategory::List(category) => {
+                let spacing = match self.is_loose {
+                    true => ListSpacing::Loose,
+                    false => ListSpacing::Tight,
+                };
+
+                Block::Container(Container::List(category, spacing, self.children), span)
+            }
+            OpenBlockCategory::ListItem(_) => {
+                Block::Container(Container::ListItem(self.children), span)
+            }
+            OpenBlockCategory::Paragraph => Block::Leaf(
+                Leaf::new(LeafCategory::Paragraph, Paragraph::raw_content(&self.lines))
+                    .with_source(span, self.content_offsets),
+            ),
+            OpenBlockCategory::IndentedCodeBlock => Block::Leaf(
+                Leaf::new(
+                    LeafCategory::IndentedCodeBlock,
+                    IndentedCodeBlock::raw_content(&self.lines),
+                )
+                .with_source(span, Vec::new()),
+            ),
+            OpenBlockCategory::FencedCodeBlock(opening_fence) => Block::Leaf(
+                Leaf::new(
+                    LeafCategory::FencedCodeBlock(opening_fence.info_string),
+                    self.lines,
+                )
+                .with_source(span, Vec::new()),
+            ),
+            OpenBlockCategory::HtmlBlock(_) => Block::Leaf(
+                Leaf::new(LeafCategory::HtmlBlock, self.lines).with_source(span, Vec::new()),
+            ),
+        };
+
+        Some(block)
+    }
+}
+
+// [SPEC]: https://spec.commonmark.org/0.30/#appendix-a-parsing-strategy
+// The first phase of parsing, which builds the block structure of the document one line at a
+// time. The blocks that are still open form a chain from the document down to the last block
+// added, kept here as a stack. Each line continues some of them, may close the rest, and may
+// open new ones. Inline content is only parsed once all blocks are closed.
+pub struct OpenBlocks<'a> {
+    stack: Vec<OpenBlock>,
+    source_map: &'a SourceMap,
+    // Offsets in the normalized input of the start of the current line, the end of its content,
+    // and its end after the line ending.
+    line_start: usize,
+    content_end: usize,
+    line_end: usize,
+}
+
+impl<'a> OpenBlocks<'a> {
+    pub fn new(source_map: &'a SourceMap) -> OpenBlocks<'a> {
+        OpenBlocks {
+            stack: vec![OpenBlock::new(OpenBlockCategory::Document, 0, 0)],
+            source_map,
+            line_start: 0,
+            content_end: 0,
+            line_end: 0,
+        }
+    }
+
+    fn is_blank(line: &str, document: &Document) -> bool {
+        document
+            .preliminaries
+            .blank_line
+            .try_match(String::from(line))
+            .is_ok()
+    }
+
+    // The rest of the line is what's left of the line once some of its start is consumed. Tabs
+    // expanded into spaces may make it longer than what it's left of, in which case its offset is
+    // that of the start of the line.
+    fn offset_of(&self, rest_of_line: &str) -> usize {
+        self.line_end
+            .saturating_sub(rest_of_line.len())
+            .max(self.line_start)
+    }
+
+    fn tip(&mut self) -> &mut OpenBlock {
+        let tip_index = self.stack.len() - 1;
+
+        &mut self.stack[tip_index]
+    }
+
+    fn close_tip(&mut self) {
+        if self.stack.len() == 1 {
+            return;
+        }
+
+        if let Some(open_block) = self.stack.pop() {
+            let ends_with_blank_line = open_block.last_line_blank
+                || (open_block.is_list_or_list_item()
+                    && open_block.last_child_ends_with_blank_line);
+            let is_loose_list_item = matches!(open_block.category, OpenBlockCategory::ListItem(_))
+                && open_block.is_loose;
+
+            if let Some(block) = open_block.into_block(self.source_map) {
+                let parent = self.tip();
+
+                if is_loose_list_item {
+                    parent.is_loose = true;
+                }
+
+                parent.add_child(block, ends_with_blank_line);
+            }
+        }
+    }
+
+    fn close_unmatched(&mut self, matched_count: usize) {
+        while self.stack.len() > matched_count {
+            self.close_tip();
+        }
+    }
+
+    // Closes blocks until one can contain the new block.
+    fn open(&mut self, category: OpenBlockCategory, start: usize) {
+        while !self.tip().can_contain(&category) {
+            self.close_tip();
+        }
+
+        self.stack
+            .push(OpenBlock::new(category, start, self.content_end));
+    }
+
+    // Leaves that only ever span one line are closed right away.
+    fn add_leaf(
+        &mut self,
+        category: LeafCategory,
+        text: String,
+        start: usize,
+        content_offsets: Vec<(usize, usize)>,
+    ) {
+        while !self.tip().can_contain(&OpenBlockCategory::Paragraph) {
+            self.close_tip();
+        }
+
+        let span = self.source_map.span(start, self.content_end);
+
+        self.tip().add_child(
+            Block::Leaf(Leaf::new(category, text).with_source(span, content_offsets)),
+            false,
+        );
+    }
+
+    // The spaces or tabs at the start of each line of a paragraph aren't part of its content. Each
+    // line of code ends with a line ending, even the last line of the input.
+    fn add_to_tip(&mut self, rest: &str) {
+        let content = rest.trim_start_matches(is_space_or_tab);
+        let content_offset = self.offset_of(content);
+
+        let tip = self.tip();
+
+        match tip.category {
+            OpenBlockCategory::Paragraph => {
+                tip.content_offsets.push((tip.lines.len(), content_offset));
+                tip.lines.push_str(content);
+            }
+            OpenBlockCategory::IndentedCodeBlock | OpenBlockCategory::FencedCodeBlock(_) => {
+                tip.lines.push_str(rest);
+
+                if !rest.ends_with(is_line_ending) {
+                    tip.lines.push(NEWLINE);
+                }
+            }
+            _ => tip.lines.push_str(rest),
+        }
+    }
+
+    // Consumes the markers of each open block that the line continues, returning how many of them
+    // it continues, along with the rest of the line. Returns `None` if the line closes a fenced
+    // code block, and has nothing left to add.
+    fn continue_open_blocks(
+        &mut self,
+        line: String,
+        document: &Document,
+    ) -> Option<(usize, String)> {
+        let indentation = &document.preliminaries.indentation;
+
+        let mut matched_count = 1;
+        let mut rest = line;
+
+        while matched_count < self.stack.len() {
+            let is_blank = OpenBlocks::is_blank(&rest, document);
+            let has_children = !self.stack[matched_count].children.is_empty()
+                || matched_count + 1 < self.stack.len();
+
+            let continued = match &self.stack[matched_count].category {
+                OpenBlockCategory::Document | OpenBlockCategory::List(_) => Ok(rest),
+                OpenBlockCategory::BlockQuote => BlockQuote::strip_delimiter(rest, document),
+                OpenBlockCategory::ListItem(content_indentation) => {
+                    if indentation.width(&rest, 0) >= *content_indentation {
+                        Ok(indentation.strip(&rest, 0, *content_indentation))
+                    } else if is_blank && has_children {
+                        // A list item can begin with at most one blank line, so a blank line only
+                        // continues an item once it has content.
+                        Ok(String::from(rest.trim_start_matches(is_space_or_tab)))
+                    } else {
+                        Err(rest)
+                    }
+                }
+                OpenBlockCategory::Paragraph => match is_blank {
+                    true => Err(rest),
+                    false => Ok(rest),
+                },
+                OpenBlockCategory::IndentedCodeBlock => {
+                    if is_blank || indentation.width(&rest, 0) >= CODE_BLOCK_INDENTATION {
+                        Ok(indentation.strip(&rest, 0, CODE_BLOCK_INDENTATION))
+                    } else {
+                        Err(rest)
+                    }
+                }
+                OpenBlockCategory::FencedCodeBlock(opening_fence) => {
+                    // The closing fence is part of the code block, and of every block it's in.
+                    if opening_fence.is_closed_by(&rest) {
+                        for open_block in &mut self.stack {
+                            open_block.end = self.content_end;
+                        }
+
+                        self.close_tip();
+                        return None;
+                    }
+
+                    Ok(indentation.strip(&rest, 0, opening_fence.indentation))
+                }
+                OpenBlockCategory::HtmlBlock(end) => {
+                    match is_blank && *end == HtmlBlockEnd::BlankLine {
+                        true => Err(rest),
+                        false => Ok(rest),
+                    }
+                }
+            };
+
+            match continued {
+                Ok(leftover_from_open_block) => {
+                    matched_count += 1;
+                    rest = leftover_from_open_block;
+                }
+                Err(rejected_from_open_block) => {
+                    rest = rejected_from_open_block;
+                    break;
+                }
+            }
+        }
+
+        Some((matched_count, rest))
+    }
+
+    // Takes the line along with its offset in the normalized input. Blocks that the line is part
+    // of end at the end of its content, except for blank lines, which only belong to blocks that
+    // keep them.
+    pub fn add_line(&mut self, line: String, offset: usize, document: &Document) {
+        self.line_start = offset;
+        self.content_end = offset + line.trim_end_matches(is_line_ending).len();
+        self.line_end = offset + line.len();
+
+        let is_blank = OpenBlocks::is_blank(&line, document);
+
+        self.add_line_content(line, document);
+
+        if !is_blank || self.tip().keeps_blank_lines() {
+            let content_end = self.content_end;
+
+            for open_block in &mut self.stack {
+                open_block.end = content_end;
+            }
+        }
+    }
+
+    fn add_line_content(&mut self, line: String, document: &Document) {
+        let (mut matched_count, mut rest) = match self.continue_open_blocks(line, document) {
+            Some(continued) => continued,
+            None => return,
+        };
+
+        // A line that doesn't continue every open block may still be a lazy continuation line of
+        // an open paragraph, unless it starts a new block.
+        let mut maybe_lazy = matches!(self.tip().category, OpenBlockCategory::Paragraph);
+        let mut has_new_blocks = false;
+
+        loop {
+            // The container is the last open block that the line continues, or the last one it
+            // starts. Only containers and paragraphs may hold the start of a new block.
+            let container = &self.stack[matched_count - 1];
+
+            if matches!(
+                container.category,
+                OpenBlockCategory::IndentedCodeBlock
+                    | OpenBlockCategory::FencedCodeBlock(_)
+                    | OpenBlockCategory::HtmlBlock(_)
+            ) {
+                break;
+            }
+
+            let is_paragraph_container = matches!(container.category, OpenBlockCategory::Paragraph);
+
+            let is_blank = OpenBlocks::is_blank(&rest, document);
+
+            // Blocks start at their first character other than a space or tab.
+            let start = self.offset_of(rest.trim_start_matches(is_space_or_tab));
+
+            if document.preliminaries.indentation.width(&rest, 0) >= CODE_BLOCK_INDENTATION {
+                // An indented code block can't interrupt a paragraph.
+                if !maybe_lazy && !is_blank {
+                    let content =
+                        document
+                            .preliminaries
+                            .indentation
+                            .strip(&rest, 0, CODE_BLOCK_INDENTATION);
+                    let content_start = self.offset_of(&content);
+
+                    self.close_unmatched(matched_count);
+                    self.open(OpenBlockCategory::IndentedCodeBlock, content_start);
+                    self.add_to_tip(&content);
+                    return;
+                }
+
+                break;
+            }
+
+            rest = match BlockQuote::strip_delimiter(rest, document) {
+                Ok(leftover_from_delimiter) => {
+                    self.close_unmatched(matched_count);
+                    self.open(OpenBlockCategory::BlockQuote, start);
+
+                    has_new_blocks = true;
+                    maybe_lazy = false;
+                    matched_count = self.stack.len();
+                    rest = leftover_from_delimiter;
+                    continue;
+                }
+                Err(rejected_from_block_quote) => rejected_from_block_quote,
+            };
+
+            let heading_content_start = self.offset_of(
+                rest.trim_start_matches(is_space_or_tab)
+                    .trim_start_matches(ATX_HEADING_DELIMITER)
+                    .trim_start_matches(is_space_or_tab),
+            );
+
+            rest = match AtxHeading::try_parse(rest, document) {
+                TryParseResult::Accepted(ParseResult((level, content), _)) => {
+                    self.close_unmatched(matched_count);
+                    self.add_leaf(
+                        LeafCategory::AtxHeading(level),
+                        content,
+                        start,
+                        vec![(0, heading_content_start)],
+                    );
+                    return;
+                }
+                TryParseResult::Rejected(rejected_from_atx_heading) => rejected_from_atx_heading,
+            };
+
+            rest = match FencedCodeBlock::try_parse(rest, document) {
+                TryParseResult::Accepted(ParseResult(FencedCodeBlockStart::Open(fence), _)) => {
+                    self.close_unmatched(matched_count);
+                    self.open(OpenBlockCategory::FencedCodeBlock(fence), start);
+                    return;
+                }
+                TryParseResult::Accepted(ParseResult(FencedCodeBlockStart::Closed(content), _)) => {
+                    self.close_unmatched(matched_count);
+                    self.add_leaf(
+                        LeafCategory::FencedCodeBlock(None),
+                        content,
+                        start,
+                        Vec::new(),
+                    );
+                    return;
+                }
+                TryParseResult::Rejected(rejected_from_fenced_code_block) => {
+                    rejected_from_fenced_code_block
+                }
+            };
+
+            if let Some((end, can_interrupt_paragraph)) =
+                HtmlBlock::start_condition(&rest, document)
+            {
+                if can_interrupt_paragraph || !is_paragraph_container {
+                    self.close_unmatched(matched_count);
+
+                    let is_closed = end.is_met_by(&rest);
+                    self.open(OpenBlockCategory::HtmlBlock(end), start);
+                    self.add_to_tip(&rest);
+
+                    if is_closed {
+                        self.close_tip();
+                    }
+
+                    return;
+                }
+            }
+
+            if is_paragraph_container {
+                // [SPEC]: https://spec.commonmark.org/0.30/#example-218
+                // Once a paragraph that only holds link reference definitions loses them, nothing
+                // is left to be a heading, so the underline is read as any other line instead.
+                rest = match SetextHeadingUnderline::try_parse(rest.clone(), document) {
+                    TryParseResult::Accepted(ParseResult(level, _))
+                        if !LinkReferenceDefinition::is_whole_content(
+                            Paragraph::raw_content(&self.tip().lines),
+                            document,
+                        ) =>
+                    {
+                        if let Some(paragraph) = self.stack.pop() {
+                            let span = self.source_map.span(paragraph.start, self.content_end);
+
+                            self.tip().add_child(
+                                Block::Leaf(
+                                    Leaf::new(
+                                        LeafCategory::SetextHeading(level),
+                                        Paragraph::raw_content(&paragraph.lines),
+                                    )
+                                    .with_source(span, paragraph.content_offsets),
+                                ),
+                                false,
+                            );
+                        }
+
+                        return;
+                    }
+                    _ => rest,
+                };
+            }
+
+            rest = match ThematicBreak::try_parse(rest, document) {
+                TryParseResult::Accepted(_) => {
+                    self.close_unmatched(matched_count);
+                    self.add_leaf(
+                        LeafCategory::ThematicBreak,
+                        String::new(),
+                        start,
+                        Vec::new(),
+                    );
+                    return;
+                }
+                TryParseResult::Rejected(rejected_from_thematic_break) => {
+                    rejected_from_thematic_break
+                }
+            };
+
+            if is_paragraph_container
+                && !ListMarker::can_interrupt_paragraph(rest.clone(), document)
+            {
+                break;
+            }
+
+            rest = match ListMarker::try_parse(rest, document) {
+                TryParseResult::Accepted(ParseResult(
+                    (category, content_indentation),
+                    optional_leftover_from_list_marker,
+                )) => {
+                    self.close_unmatched(matched_count);
+
+                    // Closing leaves first reveals whether the item continues an open list.
+                    while !self.tip().can_contain(&OpenBlockCategory::List(category)) {
+                        if let OpenBlockCategory::List(_) = self.tip().category {
+                            break;
+                        }
+
+                        self.close_tip();
+                    }
+
+                    let is_same_list = match self.tip().category {
+                        OpenBlockCategory::List(list_category) => {
+                            list_category.is_same_list_as(&category)
+                        }
+                        _ => false,
+                    };
+
+                    if !is_same_list {
+                        self.open(OpenBlockCategory::List(category), start);
+                    }
+
+                    self.open(OpenBlockCategory::ListItem(content_indentation), start);
+
+                    has_new_blocks = true;
+                    maybe_lazy = false;
+                    matched_count = self.stack.len();
+                    rest = optional_leftover_from_list_marker.unwrap_or_default();
+                    continue;
+                }
+                TryParseResult::Rejected(rejected_from_list_marker) => rejected_from_list_marker,
+            };
+
+            break;
+        }
+
+        self.add_text(matched_count, has_new_blocks, rest, document);
+    }
+
+    // Adds what remains of the line to the last open block, or to a new paragraph.
+    fn add_text(
+        &mut self,
+        matched_count: usize,
+        has_new_blocks: bool,
+        rest: String,
+        document: &Document,
+    ) {
+        let is_blank = OpenBlocks::is_blank(&rest, document);
+
+        if !has_new_blocks
+            && matched_count < self.stack.len()
+            && !is_blank
+            && matches!(self.tip().category, OpenBlockCategory::Paragraph)
+        {
+            self.add_to_tip(&rest);
+            return;
+        }
+
+        self.close_unmatched(matched_count);
+
+        let tip_index = self.stack.len() - 1;
+
+        for open_block in &mut self.stack[..tip_index] {
+            open_block.last_line_blank = false;
+        }
+
+        let tip = self.tip();
+
+        // Blank lines at the start of a list item, in a block quote or in a fenced code block
+        // don't count towards making a list loose.
+        tip.last_line_blank = is_blank
+            && !matches!(
+                tip.category,
+                OpenBlockCategory::BlockQuote | OpenBlockCategory::FencedCodeBlock(_)
+            )
+            && !(matches!(tip.category, OpenBlockCategory::ListItem(_))
+                && tip.children.is_empty()
+                && has_new_blocks);
+
+        if is_blank && !tip.children.is_empty() {
+            tip.last_child_ends_with_blank_line = true;
+        }
+
+        match &tip.category {
+            OpenBlockCategory::Paragraph
+            | OpenBlockCategory::IndentedCodeBlock
+            | OpenBlockCategory::FencedCodeBlock(_) => self.add_to_tip(&rest),
+            OpenBlockCategory::HtmlBlock(end) => {
+                let is_closed = end.is_met_by(&rest);
+                self.add_to_tip(&rest);
+
+                if is_closed {
+                    self.tip().end = self.content_end;
+                    self.close_tip();
+                }
+            }
+            _ => {
+                if !is_blank {
+                    let start = self.offset_of(rest.trim_start_matches(is_space_or_tab));
+
+                    self.open(OpenBlockCategory::Paragraph, start);
+                    self.add_to_tip(&rest);
+                }
+            }
+        }
+    }
+
+    // Closes every block that is still open, and returns the blocks of the document.
+    pub fn close_all(mut self) -> Vec<Block> {
+        while self.stack.len() > 1 {
+            self.close_tip();
+        }
+
+        self.stack
+            .pop()
+            .map(|document_block| document_block.children)
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Block, Container, Document, OpenBlocks};
+
+    fn describe(blocks: &[Block]) -> String {
+        blocks
+            .iter()
+            .map(|block| match block {
+                Block::Container(Container::BlockQuote(children), _) => {
+                    format!("BlockQuote[{}]", describe(children))
+                }
+                Block::Container(Container::List(category, spacing, items), _) => {
+                    format!("List({:?}, {:?})[{}]", category, spacing, describe(items))
+                }
+                Block::Container(Container::ListItem(children), _) => {
+                    format!("ListItem[{}]", describe(children))
+                }
+                Block::Leaf(leaf) => format!("{:?}({:?})", leaf.category, leaf.text),
+            })
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+
+    fn parse_blocks(input: &str, document: &Document) -> Vec<Block> {
+        let mut open_blocks = OpenBlocks::new(&document.source_map);
+        let mut offset = 0;
+
+        for line in input.split_inclusive('\n') {
+            open_blocks.add_line(String::from(line), offset, document);
+            offset += line.len();
+        }
+
+        open_blocks.close_all()
+    }
+
+    fn parse(input: &str) -> String {
+        describe(&parse_blocks(input, &Document::new()))
+    }
+
+    #[test]
+    fn it_nests_containers_arbitrarily() {
+        assert_eq!(
+            parse("> 1. > - foo\n>    >   bar\n"),
+            "BlockQuote[List(Ordered(1, '.'), Tight)[ListItem[BlockQuote[List(Bullet('-'), \
+             Tight)[ListItem[Paragraph(\"foo\\nbar\")]]]]]]"
+        );
+    }
+
+    #[test]
+    fn it_adds_lazy_continuation_lines_to_open_paragraph() {
+        assert_eq!(
+            parse("> > foo\nbar\n- baz\nqux\n"),
+            "BlockQuote[BlockQuote[Paragraph(\"foo\\nbar\")]], \
+             List(Bullet('-'), Tight)[ListItem[Paragraph(\"baz\\nqux\")]]"
+        );
+    }
+
+    #[test]
+    fn it_closes_unmatched_blocks_on_lines_that_are_not_lazy() {
+        assert_eq!(
+            parse("> foo\n---\n"),
+            "BlockQuote[Paragraph(\"foo\")], ThematicBreak(\"\")"
+        );
+        assert_eq!(
+            parse("> ```\n> foo\nbar\n"),
+            "BlockQuote[FencedCodeBlock(None)(\"foo\\n\")], Paragraph(\"bar\")"
+        );
+        assert_eq!(
+            parse(">     foo\n    bar\n"),
+            "BlockQuote[IndentedCodeBlock(\"foo\\n\")], IndentedCodeBlock(\"bar\\n\")"
+        );
+    }
+
+    #[test]
+    fn it_interrupts_paragraphs() {
+        assert_eq!(
+            parse("foo\n# bar\nbaz\n> qux\n"),
+            "Paragraph(\"foo\"), AtxHeading(One)(\"bar\"), Paragraph(\"baz\"), \
+             BlockQuote[Paragraph(\"qux\")]"
+        );
+        assert_eq!(
+            parse("foo\n- bar\n"),
+            "Paragraph(\"foo\"), List(Bullet('-'), Tight)[ListItem[Paragraph(\"bar\")]]"
+        );
+    }
+
+    #[test]
+    fn it_does_not_interrupt_paragraphs_with_some_blocks() {
+        assert_eq!(
+            parse("foo\n    bar\n2. baz\n*\n<x-y>\n"),
+            "Paragraph(\"foo\\nbar\\n2. baz\\n*\\n<x-y>\")"
+        );
+    }
+
+    #[test]
+    fn it_turns_paragraphs_followed_by_underline_into_setext_headings() {
+        assert_eq!(
+            parse("foo\nbar\n===\n- baz\n  ---\n"),
+            "SetextHeading(One)(\"foo\\nbar\"), \
+             List(Bullet('-'), Tight)[ListItem[SetextHeading(Two)(\"baz\")]]"
+        );
+    }
+
+    #[test]
+    fn it_keeps_blank_lines_in_code_blocks() {
+        assert_eq!(
+            parse("- ```\n  foo\n\n\n  bar\n  ```\n-     baz\n\n      qux\n\n\n"),
+            "List(Bullet('-'), Tight)[ListItem[FencedCodeBlock(None)(\"foo\\n\\n\\nbar\\n\")], \
+             ListItem[IndentedCodeBlock(\"baz\\n\\nqux\\n\")]]"
+        );
+    }
+
+    #[test]
+    fn it_ends_html_blocks_on_end_condition() {
+        assert_eq!(
+            parse("<div>\n*foo*\n\nbar\n<!-- baz\n\n--> qux\nquux\n"),
+            "HtmlBlock(\"<div>\\n*foo*\\n\"), Paragraph(\"bar\"), \
+             HtmlBlock(\"<!-- baz\\n\\n--> qux\\n\"), Paragraph(\"quux\")"
+        );
+    }
+
+    #[test]
+    fn it_ends_list_items_on_less_indented_lines() {
+        assert_eq!(
+            parse("1. foo\n\n   bar\n\n  baz\n"),
+            "List(Ordered(1, '.'), Loose)[ListItem[Paragraph(\"foo\"), Paragraph(\"bar\")]], \
+             Paragraph(\"baz\")"
+        );
+        assert_eq!(
+            parse("-\n\n  foo\n"),
+            "List(Bullet('-'), Tight)[ListItem[]], Paragraph(\"foo\")"
+        );
+    }
+
+    #[test]
+    fn it_starts_new_list_when_marker_type_changes() {
+        assert_eq!(
+            parse("- foo\n+ bar\n1. baz\n2) qux\n"),
+            "List(Bullet('-'), Tight)[ListItem[Paragraph(\"foo\")]], \
+             List(Bullet('+'), Tight)[ListItem[Paragraph(\"bar\")]], \
+             List(Ordered(1, '.'), Tight)[ListItem[Paragraph(\"baz\")]], \
+             List(Ordered(2, ')'), Tight)[ListItem[Paragraph(\"qux\")]]"
+        );
+    }
+
+    #[test]
+    fn it_makes_lists_with_blank_lines_between_blocks_loose() {
+        assert_eq!(
+            parse("- foo\n\n- bar\n"),
+            "List(Bullet('-'), Loose)[ListItem[Paragraph(\"foo\")], ListItem[Paragraph(\"bar\")]]"
+        );
+        assert_eq!(
+            parse("- foo\n  - bar\n\n    baz\n- qux\n"),
+            "List(Bullet('-'), Tight)[ListItem[Paragraph(\"foo\"), \
+             List(Bullet('-'), Loose)[ListItem[Paragraph(\"bar\"), Paragraph(\"baz\")]]], \
+             ListItem[Paragraph(\"qux\")]]"
+        );
+    }
+
+    #[test]
+    fn it_keeps_lists_with_blank_lines_only_at_the_end_tight() {
+        assert_eq!(
+            parse("- foo\n- bar\n\n\nbaz\n"),
+            "List(Bullet('-'), Tight)[ListItem[Paragraph(\"foo\")], ListItem[Paragraph(\"bar\")]], \
+             Paragraph(\"baz\")"
+        );
+        assert_eq!(
+            parse("- ```\n  foo\n\n  ```\n- bar\n"),
+            "List(Bullet('-'), Tight)[ListItem[FencedCodeBlock(None)(\"foo\\n\\n\")], \
+             ListItem[Paragraph(\"bar\")]]"
+        );
+    }
+
+    #[test]
+    fn it_ends_last_line_of_code_with_line_ending() {
+        assert_eq!(parse("    foo"), "IndentedCodeBlock(\"foo\\n\")");
+        assert_eq!(parse("```\nfoo"), "FencedCodeBlock(None)(\"foo\\n\")");
+    }
+
+    #[test]
+    fn it_spans_blocks_from_their_first_character_to_their_last_line() {
+        fn spans(blocks: &[Block]) -> Vec<(usize, usize)> {
+            blocks
+                .iter()
+                .flat_map(|block| {
+                    let span = block.span();
+                    let children = match block {
+                        Block::Container(Container::BlockQuote(children), _)
+                        | Block::Container(Container::List(_, _, children), _)
+                        | Block::Container(Container::ListItem(children), _) => spans(children),
+                        Block::Leaf(_) => Vec::new(),
+                    };
+
+                    std::iter::once((span.start.offset, span.end.offset)).chain(children)
+                })
+                .collect()
+        }
+
+        let document = Document::new();
+
+        assert_eq!(
+            spans(&parse_blocks(
+                "> - a\nb\n\n  ```\n  c\n\n  ```\n      d\n",
+                &document
+            )),
+            vec![(0, 7), (2, 7), (2, 7), (4, 7), (11, 25), (30, 33)]
+        );
+    }
+}
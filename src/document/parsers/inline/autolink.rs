@@ -0,0 +1,148 @@
+use super::{Document, ParseResult, TryParse, TryParseResult};
+
+const AUTOLINK_OPENER: char = '<';
+const AUTOLINK_CLOSER: char = '>';
+const SCHEME_SEPARATOR: char = ':';
+const EMAIL_SEPARATOR: char = '@';
+const EMAIL_SCHEME: &str = "mailto:";
+const MAXIMUM_DOMAIN_LABEL_LENGTH: usize = 63;
+
+// [SPEC]: https://spec.commonmark.org/0.30/#autolinks
+pub struct Autolink;
+
+impl Autolink {
+    // [SPEC]: https://spec.commonmark.org/0.30/#absolute-uri
+    fn is_absolute_uri(text: &str) -> bool {
+        let (scheme, _) = match text.split_once(SCHEME_SEPARATOR) {
+            Some(split) => split,
+            None => return false,
+        };
+
+        let is_valid_scheme = (2..=32).contains(&scheme.len())
+            && scheme.starts_with(|character: char| character.is_ascii_alphabetic())
+            && scheme
+                .chars()
+                .all(|character| character.is_ascii_alphanumeric() || "+.-".contains(character));
+
+        is_valid_scheme
+            && !text.contains(|character: char| {
+                character.is_ascii_control() || character == ' ' || character == '<'
+            })
+    }
+
+    // [SPEC]: https://spec.commonmark.org/0.30/#email-address
+    fn is_email_address(text: &str) -> bool {
+        let (local_part, domain) = match text.split_once(EMAIL_SEPARATOR) {
+            Some(split) => split,
+            None => return false,
+        };
+
+        let is_valid_local_part = !local_part.is_empty()
+            && local_part.chars().all(|character| {
+                character.is_ascii_alphanumeric() || ".!#$%&'*+/=?^_`{|}~-".contains(character)
+            });
+
+        let is_valid_domain = domain.split('.').all(|label| {
+            (1..=MAXIMUM_DOMAIN_LABEL_LENGTH).contains(&label.len())
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label
+                    .chars()
+                    .all(|character| character.is_ascii_alphanumeric() || character == '-')
+        });
+
+        is_valid_local_part && is_valid_domain
+    }
+}
+
+// Returns the destination of the link, and its text, which is the destination as written.
+impl TryParse<(String, String)> for Autolink {
+    fn try_parse(input: String, _document: &Document) -> TryParseResult<(String, String)> {
+        let (text, leftover) = match input
+            .strip_prefix(AUTOLINK_OPENER)
+            .and_then(|after_opener| after_opener.split_once(AUTOLINK_CLOSER))
+        {
+            Some(split) => split,
+            None => return TryParseResult::Rejected(input),
+        };
+
+        let destination = if Autolink::is_absolute_uri(text) {
+            String::from(text)
+        } else if Autolink::is_email_address(text) {
+            [EMAIL_SCHEME, text].concat()
+        } else {
+            return TryParseResult::Rejected(input);
+        };
+
+        TryParseResult::Accepted(ParseResult::new(
+            (destination, String::from(text)),
+            String::from(leftover),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Autolink, Document, ParseResult, TryParse, TryParseResult};
+
+    #[test]
+    fn it_accepts_absolute_uris() {
+        let document = Document::new();
+
+        assert_eq!(
+            Autolink::try_parse(String::from("<https://example.com/?q=a>b"), &document),
+            TryParseResult::Accepted(ParseResult(
+                (
+                    String::from("https://example.com/?q=a"),
+                    String::from("https://example.com/?q=a")
+                ),
+                Some(String::from("b"))
+            ))
+        );
+
+        assert_eq!(
+            Autolink::try_parse(String::from("<a+b+c:d>"), &document),
+            TryParseResult::Accepted(ParseResult(
+                (String::from("a+b+c:d"), String::from("a+b+c:d")),
+                None
+            ))
+        );
+    }
+
+    #[test]
+    fn it_accepts_email_addresses() {
+        let document = Document::new();
+
+        assert_eq!(
+            Autolink::try_parse(String::from("<foo+special@Bar.baz-bar0.com>"), &document),
+            TryParseResult::Accepted(ParseResult(
+                (
+                    String::from("mailto:foo+special@Bar.baz-bar0.com"),
+                    String::from("foo+special@Bar.baz-bar0.com")
+                ),
+                None
+            ))
+        );
+    }
+
+    #[test]
+    fn it_rejects_invalid_autolinks() {
+        let document = Document::new();
+
+        for input in [
+            "<>",
+            "< https://foo.bar >",
+            "<m:abc>",
+            "<foo.bar.baz>",
+            "<https://foo.bar/baz bim>",
+            "<foo\\+@bar.example.com>",
+            "<foo@-bar.com>",
+            "https://example.com",
+        ] {
+            assert_eq!(
+                Autolink::try_parse(String::from(input), &document),
+                TryParseResult::Rejected(String::from(input))
+            );
+        }
+    }
+}
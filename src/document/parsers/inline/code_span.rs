@@ -1,9 +1,58 @@
 use super::{Document, IndividualMatcher, Matcher, ParseResult, TryParse, TryParseResult};
+use std::collections::HashMap;
 
 pub struct CodeSpan;
 
 const CODE_SPAN_DELIMITER: char = '`';
 
+// [SPEC]: https://spec.commonmark.org/0.30/#backtick-string
+// Where the last backtick string of each length starts in the text being parsed. The text is
+// indexed once, so that a backtick string that can't be closed is known to be text right away,
+// rather than after scanning the rest of the text for a closer each time.
+#[derive(Default)]
+pub struct BacktickStrings {
+    last_starts: HashMap<usize, usize>,
+}
+
+impl BacktickStrings {
+    pub fn index(text: &str) -> BacktickStrings {
+        let mut backtick_strings = BacktickStrings::default();
+        let mut string_start = None;
+
+        for (offset, character) in text.char_indices() {
+            match (character == CODE_SPAN_DELIMITER, string_start) {
+                (true, None) => string_start = Some(offset),
+                (false, Some(start)) => {
+                    backtick_strings.last_starts.insert(offset - start, start);
+                    string_start = None;
+                }
+                _ => {}
+            }
+        }
+
+        if let Some(start) = string_start {
+            backtick_strings
+                .last_starts
+                .insert(text.len() - start, start);
+        }
+
+        backtick_strings
+    }
+
+    // Whether the input, found at the offset in the text, starts with a backtick string that no
+    // backtick string of the same length follows.
+    pub fn is_unclosed_at(&self, input: &str, offset: usize) -> bool {
+        let length = input.len() - input.trim_start_matches(CODE_SPAN_DELIMITER).len();
+
+        length > 0
+            && self
+                .last_starts
+                .get(&length)
+                .filter(|last_start| **last_start >= offset + length)
+                .is_none()
+    }
+}
+
 impl TryParse<String> for CodeSpan {
     fn try_parse(input: String, document: &Document) -> TryParseResult<String> {
         let delimiter_matcher = IndividualMatcher::from(CODE_SPAN_DELIMITER);
@@ -17,9 +66,6 @@ impl TryParse<String> for CodeSpan {
         let mut leading_delimiter_count = 0;
         let mut trailing_delimiter_count = 0;
 
-        let mut has_leading_space = false;
-        let mut has_trailing_space = false;
-
         while !unconsumed.is_empty() {
             match delimiter_matcher.try_match(unconsumed) {
                 Ok((matched_delimiter, leftover_from_matched_delimiter)) => {
@@ -27,9 +73,9 @@ impl TryParse<String> for CodeSpan {
                     consumed = [consumed, matched_delimiter].concat();
 
                     if is_leading_delimiter_run {
-                        leading_delimiter_count = leading_delimiter_count + 1;
+                        leading_delimiter_count += 1;
                     } else {
-                        trailing_delimiter_count = trailing_delimiter_count + 1;
+                        trailing_delimiter_count += 1;
                     }
 
                     if leading_delimiter_count == trailing_delimiter_count {
@@ -59,12 +105,7 @@ impl TryParse<String> for CodeSpan {
                             .try_match(rejected_from_delimiter_matcher)
                         {
                             Ok((matched_space, leftover_from_matched_space)) => {
-                                if is_leading_delimiter_run {
-                                    is_leading_delimiter_run = false;
-                                    has_leading_space = true;
-                                } else {
-                                    has_trailing_space = true;
-                                }
+                                is_leading_delimiter_run = false;
 
                                 consumed = [consumed, matched_space].concat();
                                 unconsumed = leftover_from_matched_space;
@@ -72,7 +113,6 @@ impl TryParse<String> for CodeSpan {
                             }
                             Err(rejected_from_space_matcher) => {
                                 is_leading_delimiter_run = false;
-                                has_trailing_space = false;
 
                                 match document
                                     .preliminaries
@@ -109,7 +149,7 @@ impl TryParse<String> for CodeSpan {
 
             while delimiter_count > 0 {
                 delimiter_run_to_strip.push(CODE_SPAN_DELIMITER);
-                delimiter_count = delimiter_count - 1;
+                delimiter_count -= 1;
             }
 
             let delimiter_run_matcher = IndividualMatcher::from(delimiter_run_to_strip);
@@ -167,44 +207,49 @@ impl TryParse<String> for CodeSpan {
                 }
             }
 
-            if has_leading_space && has_trailing_space {
+            // Line endings have been converted to spaces by now, so they're stripped as well.
+            let space = document.preliminaries.space.get_literal();
+
+            if normalized.starts_with(&space) && normalized.ends_with(&space) {
                 let is_only_spaces = normalized.contains(|character| {
-                    match document
+                    document
                         .preliminaries
                         .space
                         .try_match(String::from(character))
-                    {
-                        Ok(_) => false,
-                        Err(_) => true,
-                    }
+                        .is_err()
                 });
 
                 if is_only_spaces {
-                    let mut normalized_chars = normalized.chars();
-                    normalized_chars.next();
-
-                    normalized = normalized_chars.collect();
-
-                    let mut reversed_normalized_chars = normalized.chars().rev();
-                    reversed_normalized_chars.next();
-
-                    normalized = reversed_normalized_chars.collect();
+                    normalized =
+                        String::from(&normalized[space.len()..normalized.len() - space.len()]);
                 }
             }
 
-            return TryParseResult::Accepted(ParseResult::new(
+            TryParseResult::Accepted(ParseResult::new(
                 normalized,
                 [consumed, unconsumed].concat(),
-            ));
+            ))
         } else {
-            return TryParseResult::Rejected([consumed, unconsumed].concat());
+            TryParseResult::Rejected([consumed, unconsumed].concat())
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{CodeSpan, Document, ParseResult, TryParse, TryParseResult};
+    use super::{BacktickStrings, CodeSpan, Document, ParseResult, TryParse, TryParseResult};
+
+    #[test]
+    fn it_finds_backtick_strings_without_closer() {
+        let text = "``a`b``c```";
+        let backtick_strings = BacktickStrings::index(text);
+
+        assert!(!backtick_strings.is_unclosed_at(text, 0));
+        assert!(backtick_strings.is_unclosed_at(&text[3..], 3));
+        assert!(backtick_strings.is_unclosed_at(&text[5..], 5));
+        assert!(backtick_strings.is_unclosed_at(&text[8..], 8));
+        assert!(!backtick_strings.is_unclosed_at(&text[2..], 2));
+    }
 
     #[test]
     fn it_parses_basic_example() {
@@ -285,4 +330,24 @@ mod tests {
             TryParseResult::Accepted(ParseResult(String::from("foo bar"), None))
         );
     }
+
+    #[test]
+    fn it_strips_line_endings_converted_to_spaces() {
+        let document = Document::new();
+
+        assert_eq!(
+            CodeSpan::try_parse(String::from("``\r\nfoo\nbar  \r\n``"), &document),
+            TryParseResult::Accepted(ParseResult(String::from("foo bar  "), None))
+        );
+    }
+
+    #[test]
+    fn it_keeps_order_of_content_when_stripping_spaces() {
+        let document = Document::new();
+
+        assert_eq!(
+            CodeSpan::try_parse(String::from("` ab `"), &document),
+            TryParseResult::Accepted(ParseResult(String::from("ab"), None))
+        );
+    }
 }
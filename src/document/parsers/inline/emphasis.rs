@@ -0,0 +1,295 @@
+use super::{Document, Inline, InlineCategory, ParseResult, TryParse, TryParseResult};
+use crate::document::preliminaries::{is_punctuation, is_unicode_whitespace, Span};
+
+const ASTERISK: char = '*';
+const UNDERSCORE: char = '_';
+
+// [SPEC]: https://spec.commonmark.org/0.30/#delimiter-run
+pub struct DelimiterRun;
+
+impl TryParse<String> for DelimiterRun {
+    fn try_parse(input: String, _document: &Document) -> TryParseResult<String> {
+        let delimiter = match input.chars().next() {
+            Some(character) if character == ASTERISK || character == UNDERSCORE => character,
+            _ => return TryParseResult::Rejected(input),
+        };
+
+        let leftover = input.trim_start_matches(delimiter);
+        let run_length = input.len() - leftover.len();
+
+        TryParseResult::Accepted(ParseResult::new(
+            String::from(&input[..run_length]),
+            String::from(leftover),
+        ))
+    }
+}
+
+struct Delimiter {
+    // Position of the delimiter run's textual content among the parsed inlines.
+    index: usize,
+    character: char,
+    count: usize,
+    original_count: usize,
+    can_open: bool,
+    can_close: bool,
+}
+
+// [SPEC]: https://spec.commonmark.org/0.30/#delimiter-stack
+#[derive(Default)]
+pub struct DelimiterStack(Vec<Delimiter>);
+
+impl DelimiterStack {
+    pub fn len(&self) -> usize {
+        let DelimiterStack(delimiters) = self;
+
+        delimiters.len()
+    }
+
+    // The start and end of the line count as whitespace, for `previous_character` and
+    // `next_character` respectively.
+    pub fn push(
+        &mut self,
+        delimiter_run: &str,
+        index: usize,
+        previous_character: Option<char>,
+        next_character: Option<char>,
+    ) {
+        let DelimiterStack(delimiters) = self;
+
+        let character = match delimiter_run.chars().next() {
+            Some(character) => character,
+            None => return,
+        };
+
+        let is_preceded_by_whitespace = previous_character.is_none_or(is_unicode_whitespace);
+        let is_preceded_by_punctuation = previous_character.is_some_and(is_punctuation);
+        let is_followed_by_whitespace = next_character.is_none_or(is_unicode_whitespace);
+        let is_followed_by_punctuation = next_character.is_some_and(is_punctuation);
+
+        // [SPEC]: https://spec.commonmark.org/0.30/#left-flanking-delimiter-run
+        let is_left_flanking = !is_followed_by_whitespace
+            && (!is_followed_by_punctuation
+                || is_preceded_by_whitespace
+                || is_preceded_by_punctuation);
+
+        // [SPEC]: https://spec.commonmark.org/0.30/#right-flanking-delimiter-run
+        let is_right_flanking = !is_preceded_by_whitespace
+            && (!is_preceded_by_punctuation
+                || is_followed_by_whitespace
+                || is_followed_by_punctuation);
+
+        // Underscores can't open or close emphasis inside of words.
+        let (can_open, can_close) = match character {
+            UNDERSCORE => (
+                is_left_flanking && (!is_right_flanking || is_preceded_by_punctuation),
+                is_right_flanking && (!is_left_flanking || is_followed_by_punctuation),
+            ),
+            _ => (is_left_flanking, is_right_flanking),
+        };
+
+        delimiters.push(Delimiter {
+            index,
+            character,
+            count: delimiter_run.len(),
+            original_count: delimiter_run.len(),
+            can_open,
+            can_close,
+        });
+    }
+
+    // A closer can't match an opener if one of them can both open and close, and the sum of
+    // their lengths is a multiple of 3, unless both lengths are.
+    fn can_match(opener: &Delimiter, closer: &Delimiter) -> bool {
+        opener.character == closer.character
+            && opener.can_open
+            && !((opener.can_close || closer.can_open)
+                && (opener.original_count + closer.original_count).is_multiple_of(3)
+                && !(opener.original_count.is_multiple_of(3)
+                    && closer.original_count.is_multiple_of(3)))
+    }
+
+    // Removes a delimiter which has been used up, along with its now empty textual content.
+    fn remove_used_delimiter(
+        inlines: &mut Vec<Inline>,
+        delimiters: &mut Vec<Delimiter>,
+        position: usize,
+    ) {
+        let index = delimiters.remove(position).index;
+
+        inlines.remove(index);
+
+        for delimiter in delimiters
+            .iter_mut()
+            .filter(|delimiter| delimiter.index > index)
+        {
+            delimiter.index -= 1;
+        }
+    }
+
+    // [SPEC]: https://spec.commonmark.org/0.30/#process-emphasis
+    // Delimiters above `stack_bottom` are matched into emphasis and removed from the stack.
+    pub fn process_emphasis(&mut self, inlines: &mut Vec<Inline>, stack_bottom: usize) {
+        let DelimiterStack(delimiters) = self;
+
+        // Lower bounds for the opener search, by the kind of closer, below which it is known
+        // that no opener can be found.
+        let mut openers_bottoms: Vec<((char, bool, usize), usize)> = Vec::new();
+
+        let mut current = stack_bottom;
+
+        while current < delimiters.len() {
+            if !delimiters[current].can_close {
+                current += 1;
+                continue;
+            }
+
+            let closer_kind = (
+                delimiters[current].character,
+                delimiters[current].can_open,
+                delimiters[current].original_count % 3,
+            );
+
+            let openers_bottom = openers_bottoms
+                .iter()
+                .find(|(kind, _)| *kind == closer_kind)
+                .map_or(stack_bottom, |(_, openers_bottom)| *openers_bottom);
+
+            let optional_opener = (openers_bottom..current).rev().find(|&opener| {
+                DelimiterStack::can_match(&delimiters[opener], &delimiters[current])
+            });
+
+            let opener = match optional_opener {
+                Some(opener) => opener,
+                None => {
+                    openers_bottoms.retain(|(kind, _)| *kind != closer_kind);
+                    openers_bottoms.push((closer_kind, current));
+
+                    if delimiters[current].can_open {
+                        current += 1;
+                    } else {
+                        delimiters.remove(current);
+                    }
+
+                    continue;
+                }
+            };
+
+            // Delimiters between the opener and the closer can no longer be matched. Since
+            // delimiters are about to be removed, the lower bounds are conservatively reset to the
+            // opener.
+            delimiters.drain(opener + 1..current);
+            let mut closer = opener + 1;
+
+            for (_, openers_bottom) in openers_bottoms.iter_mut() {
+                *openers_bottom = (*openers_bottom).min(opener);
+            }
+
+            let used_count = match delimiters[opener].count >= 2 && delimiters[closer].count >= 2 {
+                true => 2,
+                false => 1,
+            };
+
+            let opener_index = delimiters[opener].index;
+            let closer_index = delimiters[closer].index;
+
+            let children: Vec<Inline> = inlines.drain(opener_index + 1..closer_index).collect();
+            let children_count = children.len();
+
+            let category = match used_count {
+                2 => InlineCategory::StrongEmphasis(children),
+                _ => InlineCategory::Emphasis(children),
+            };
+
+            // The emphasis spans the delimiters it uses, which are taken from the end of the
+            // opener and the start of the closer. Delimiter characters are one byte long.
+            let span = Span::from_offsets(
+                inlines[opener_index].span.end.offset - used_count,
+                inlines[closer_index - children_count].span.start.offset + used_count,
+            );
+
+            inlines.insert(
+                opener_index + 1,
+                Inline::new(category, String::new()).with_span(span),
+            );
+
+            for delimiter in delimiters.iter_mut().skip(closer) {
+                delimiter.index = delimiter.index + 1 - children_count;
+            }
+
+            delimiters[opener].count -= used_count;
+            delimiters[closer].count -= used_count;
+
+            let opener_inline = &mut inlines[delimiters[opener].index];
+            opener_inline
+                .text
+                .truncate(opener_inline.text.len() - used_count);
+            opener_inline.span.end.offset -= used_count;
+
+            let closer_inline = &mut inlines[delimiters[closer].index];
+            closer_inline.text.drain(..used_count);
+            closer_inline.span.start.offset += used_count;
+
+            if delimiters[opener].count == 0 {
+                DelimiterStack::remove_used_delimiter(inlines, delimiters, opener);
+                closer -= 1;
+            }
+
+            if delimiters[closer].count == 0 {
+                DelimiterStack::remove_used_delimiter(inlines, delimiters, closer);
+            }
+
+            current = closer;
+        }
+
+        delimiters.truncate(stack_bottom);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DelimiterRun, DelimiterStack, Document, ParseResult, TryParse, TryParseResult};
+
+    #[test]
+    fn it_accepts_delimiter_run() {
+        let document = Document::new();
+
+        assert_eq!(
+            DelimiterRun::try_parse(String::from("**_foo"), &document),
+            TryParseResult::Accepted(ParseResult(String::from("**"), Some(String::from("_foo"))))
+        );
+
+        assert_eq!(
+            DelimiterRun::try_parse(String::from("foo**"), &document),
+            TryParseResult::Rejected(String::from("foo**"))
+        );
+    }
+
+    #[test]
+    fn it_determines_whether_delimiter_runs_can_open_or_close() {
+        let mut delimiter_stack = DelimiterStack::default();
+
+        delimiter_stack.push("*", 0, None, Some('a'));
+        delimiter_stack.push("*", 0, Some('a'), None);
+        delimiter_stack.push("*", 0, Some('a'), Some('b'));
+        delimiter_stack.push("_", 0, Some('a'), Some('b'));
+        delimiter_stack.push("**", 0, Some(' '), Some('"'));
+        delimiter_stack.push("_", 0, Some('"'), Some('b'));
+
+        let DelimiterStack(delimiters) = delimiter_stack;
+
+        assert_eq!(
+            delimiters
+                .iter()
+                .map(|delimiter| (delimiter.can_open, delimiter.can_close))
+                .collect::<Vec<(bool, bool)>>(),
+            vec![
+                (true, false),
+                (false, true),
+                (true, true),
+                (false, false),
+                (true, false),
+                (true, false),
+            ]
+        );
+    }
+}
@@ -0,0 +1,91 @@
+use super::{Document, InlineCategory, Matcher, ParseResult, TryParse, TryParseResult};
+use crate::document::preliminaries::{is_space_or_tab, SPACE};
+
+const BACKSLASH: char = '\\';
+const MINIMUM_HARD_BREAK_SPACE_COUNT: usize = 2;
+
+// [SPEC]: https://spec.commonmark.org/0.30/#hard-line-breaks
+// [SPEC]: https://spec.commonmark.org/0.30/#soft-line-breaks
+pub struct LineBreak;
+
+// Accepts a line ending, along with the spaces before it and the spaces or tabs at the start of the
+// next line. It is a hard break when preceded by at least two spaces or by a backslash, and a soft
+// break otherwise.
+impl TryParse<InlineCategory> for LineBreak {
+    fn try_parse(input: String, document: &Document) -> TryParseResult<InlineCategory> {
+        let after_spaces = input.trim_start_matches(SPACE);
+        let space_count = input.len() - after_spaces.len();
+
+        let (is_hard_break, line_ending) = match after_spaces.strip_prefix(BACKSLASH) {
+            Some(after_backslash) if space_count == 0 => (true, after_backslash),
+            Some(_) => return TryParseResult::Rejected(input),
+            None => (space_count >= MINIMUM_HARD_BREAK_SPACE_COUNT, after_spaces),
+        };
+
+        match document
+            .preliminaries
+            .line_ending
+            .try_match(String::from(line_ending))
+        {
+            Ok((_, leftover_from_line_ending)) => {
+                let category = match is_hard_break {
+                    true => InlineCategory::HardBreak,
+                    false => InlineCategory::SoftBreak,
+                };
+
+                TryParseResult::Accepted(ParseResult::new(
+                    category,
+                    String::from(leftover_from_line_ending.trim_start_matches(is_space_or_tab)),
+                ))
+            }
+            Err(_) => TryParseResult::Rejected(input),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Document, InlineCategory, LineBreak, ParseResult, TryParse, TryParseResult};
+
+    #[test]
+    fn it_accepts_soft_breaks() {
+        let document = Document::new();
+
+        for input in ["\n  bar", " \nbar", "\r\nbar", "\rbar"] {
+            assert_eq!(
+                LineBreak::try_parse(String::from(input), &document),
+                TryParseResult::Accepted(ParseResult(
+                    InlineCategory::SoftBreak,
+                    Some(String::from("bar"))
+                ))
+            );
+        }
+    }
+
+    #[test]
+    fn it_accepts_hard_breaks() {
+        let document = Document::new();
+
+        for input in ["  \nbar", "     \r\n\tbar", "\\\nbar", "\\\rbar"] {
+            assert_eq!(
+                LineBreak::try_parse(String::from(input), &document),
+                TryParseResult::Accepted(ParseResult(
+                    InlineCategory::HardBreak,
+                    Some(String::from("bar"))
+                ))
+            );
+        }
+    }
+
+    #[test]
+    fn it_rejects_anything_but_a_line_ending() {
+        let document = Document::new();
+
+        for input in ["  bar", " \\\nbar", "\\bar", "\\"] {
+            assert_eq!(
+                LineBreak::try_parse(String::from(input), &document),
+                TryParseResult::Rejected(String::from(input))
+            );
+        }
+    }
+}
@@ -0,0 +1,239 @@
+use super::{
+    skip_whitespace, Document, LinkDestination, LinkLabel, LinkTitle, ParseResult, TryParse,
+    TryParseResult,
+};
+
+const LINK_OPENER: &str = "[";
+const IMAGE_OPENER: &str = "![";
+const DESTINATION_OPENER: char = '(';
+const DESTINATION_CLOSER: char = ')';
+const COLLAPSED_REFERENCE: &str = "[]";
+
+pub const LINK_CLOSER: char = ']';
+
+// Accepts the `[` that opens link text, or the `![` that opens an image description.
+pub struct LinkOpener;
+
+impl TryParse<String> for LinkOpener {
+    fn try_parse(input: String, _document: &Document) -> TryParseResult<String> {
+        let opener = if input.starts_with(LINK_OPENER) {
+            LINK_OPENER
+        } else if input.starts_with(IMAGE_OPENER) {
+            IMAGE_OPENER
+        } else {
+            return TryParseResult::Rejected(input);
+        };
+
+        TryParseResult::Accepted(ParseResult::new(
+            String::from(opener),
+            String::from(&input[opener.len()..]),
+        ))
+    }
+}
+
+// [SPEC]: https://spec.commonmark.org/0.30/#inline-link
+// Accepts the destination and optional title in parentheses that directly follow the link text.
+pub struct InlineLink;
+
+impl TryParse<(String, Option<String>)> for InlineLink {
+    fn try_parse(input: String, document: &Document) -> TryParseResult<(String, Option<String>)> {
+        let before_destination = match input.strip_prefix(DESTINATION_OPENER) {
+            Some(after_opener) => skip_whitespace(after_opener),
+            None => return TryParseResult::Rejected(input),
+        };
+
+        // The destination may be omitted entirely.
+        let (destination, after_destination) =
+            match LinkDestination::try_parse(String::from(before_destination), document) {
+                TryParseResult::Accepted(ParseResult(destination, optional_leftover)) => {
+                    (destination, optional_leftover.unwrap_or_default())
+                }
+                TryParseResult::Rejected(rejected) => (String::new(), rejected),
+            };
+
+        let before_title = skip_whitespace(&after_destination);
+
+        // The title has to be separated from the destination by whitespace.
+        let (title, after_title) = match before_title.len() < after_destination.len() {
+            true => match LinkTitle::try_parse(String::from(before_title), document) {
+                TryParseResult::Accepted(ParseResult(title, optional_leftover)) => {
+                    (Some(title), optional_leftover.unwrap_or_default())
+                }
+                TryParseResult::Rejected(rejected) => (None, rejected),
+            },
+            false => (None, String::from(before_title)),
+        };
+
+        match skip_whitespace(&after_title).strip_prefix(DESTINATION_CLOSER) {
+            Some(leftover) => TryParseResult::Accepted(ParseResult::new(
+                (destination, title),
+                String::from(leftover),
+            )),
+            None => TryParseResult::Rejected(input),
+        }
+    }
+}
+
+// [SPEC]: https://spec.commonmark.org/0.30/#reference-link
+// Resolves full (`[text][label]`), collapsed (`[label][]`) and shortcut (`[label]`) references
+// against the document's link reference definitions.
+pub struct ReferenceLink;
+
+impl ReferenceLink {
+    // The link text is passed as written in the source, and the input starts right after its
+    // closing bracket.
+    pub fn try_resolve(
+        link_text: &str,
+        input: String,
+        document: &Document,
+    ) -> TryParseResult<(String, Option<String>)> {
+        let (label, leftover) = match LinkLabel::try_parse(input.clone(), document) {
+            TryParseResult::Accepted(ParseResult(label, optional_leftover)) => {
+                (label, optional_leftover.unwrap_or_default())
+            }
+            TryParseResult::Rejected(_) => {
+                // Without a label of its own, the link text has to be a valid label.
+                let is_valid_label = matches!(
+                    LinkLabel::try_parse(format!("[{}]", link_text), document),
+                    TryParseResult::Accepted(ParseResult(_, None))
+                );
+
+                if !is_valid_label {
+                    return TryParseResult::Rejected(input);
+                }
+
+                let leftover = input.strip_prefix(COLLAPSED_REFERENCE).unwrap_or(&input);
+
+                (String::from(link_text), String::from(leftover))
+            }
+        };
+
+        match document.find_link_reference(&label) {
+            Some(link_reference) => TryParseResult::Accepted(ParseResult::new(
+                (
+                    link_reference.destination.clone(),
+                    link_reference.title.clone(),
+                ),
+                leftover,
+            )),
+            None => TryParseResult::Rejected(input),
+        }
+    }
+}
+
+pub struct Bracket {
+    // Position of the opener's textual content among the parsed inlines.
+    pub index: usize,
+    // Position in the source text right after the opener, where the link text starts.
+    pub text_offset: usize,
+    pub is_image: bool,
+    pub delimiter_stack_bottom: usize,
+    is_active: bool,
+}
+
+// [SPEC]: https://spec.commonmark.org/0.30/#look-for-link-or-image
+#[derive(Default)]
+pub struct BracketStack(Vec<Bracket>);
+
+impl BracketStack {
+    pub fn push(
+        &mut self,
+        opener: &str,
+        index: usize,
+        text_offset: usize,
+        delimiter_stack_bottom: usize,
+    ) {
+        let BracketStack(brackets) = self;
+
+        brackets.push(Bracket {
+            index,
+            text_offset,
+            is_image: opener == IMAGE_OPENER,
+            delimiter_stack_bottom,
+            is_active: true,
+        });
+    }
+
+    // Returns the closest opener if it can still be used. Either way, it is removed from the
+    // stack.
+    pub fn pop_active(&mut self) -> Option<Bracket> {
+        let BracketStack(brackets) = self;
+
+        brackets.pop().filter(|bracket| bracket.is_active)
+    }
+
+    // Links may not contain other links, so once one is found, the link openers before it can't
+    // be used anymore. Image openers can, since images may contain links.
+    pub fn deactivate_link_openers(&mut self) {
+        let BracketStack(brackets) = self;
+
+        for bracket in brackets.iter_mut().filter(|bracket| !bracket.is_image) {
+            bracket.is_active = false;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Document, InlineLink, LinkOpener, ParseResult, TryParse, TryParseResult};
+
+    #[test]
+    fn it_accepts_link_and_image_openers() {
+        let document = Document::new();
+
+        assert_eq!(
+            LinkOpener::try_parse(String::from("![foo]"), &document),
+            TryParseResult::Accepted(ParseResult(String::from("!["), Some(String::from("foo]"))))
+        );
+
+        assert_eq!(
+            LinkOpener::try_parse(String::from("!foo"), &document),
+            TryParseResult::Rejected(String::from("!foo"))
+        );
+    }
+
+    #[test]
+    fn it_accepts_destination_and_title() {
+        let document = Document::new();
+
+        assert_eq!(
+            InlineLink::try_parse(String::from("( /uri\n  \"title\" ) leftover"), &document),
+            TryParseResult::Accepted(ParseResult(
+                (String::from("/uri"), Some(String::from("title"))),
+                Some(String::from(" leftover"))
+            ))
+        );
+
+        assert_eq!(
+            InlineLink::try_parse(String::from("(<my uri>)"), &document),
+            TryParseResult::Accepted(ParseResult((String::from("my uri"), None), None))
+        );
+
+        assert_eq!(
+            InlineLink::try_parse(String::from("(foo(and(bar)))"), &document),
+            TryParseResult::Accepted(ParseResult((String::from("foo(and(bar))"), None), None))
+        );
+    }
+
+    #[test]
+    fn it_accepts_empty_destination() {
+        let document = Document::new();
+
+        assert_eq!(
+            InlineLink::try_parse(String::from("()"), &document),
+            TryParseResult::Accepted(ParseResult((String::new(), None), None))
+        );
+    }
+
+    #[test]
+    fn it_rejects_invalid_destination_or_title() {
+        let document = Document::new();
+
+        for input in ["(/my uri)", "(foo(and(bar))", "(/url \"title)"] {
+            assert_eq!(
+                InlineLink::try_parse(String::from(input), &document),
+                TryParseResult::Rejected(String::from(input))
+            );
+        }
+    }
+}
@@ -1,64 +1,789 @@
+mod autolink;
 mod code_span;
+mod emphasis;
+mod line_break;
+mod link;
+mod raw_html;
+mod textual_content;
 
 use super::{
+    utils::{
+        skip_whitespace, BackslashEscape, CharacterReference, HtmlTag, LinkDestination, LinkLabel,
+        LinkTitle,
+    },
     Document, IndividualMatcher, Matcher, Parse, ParseMultiple, ParseResult, TryParse,
     TryParseResult,
 };
-use code_span::CodeSpan;
+use crate::document::preliminaries::Span;
+use autolink::Autolink;
+use code_span::{BacktickStrings, CodeSpan};
+use emphasis::{DelimiterRun, DelimiterStack};
+use line_break::LineBreak;
+use link::{BracketStack, InlineLink, LinkOpener, ReferenceLink, LINK_CLOSER};
+use raw_html::RawHtml;
+use textual_content::TextualContent;
 
-#[derive(Eq, PartialEq)]
+#[derive(Debug, Eq, PartialEq)]
 pub enum InlineCategory {
     CodeSpan,
+    Emphasis(Vec<Inline>),
+    HardBreak,
+    // Destination, optional title, and image description.
+    Image(String, Option<String>, Vec<Inline>),
+    // Destination, optional title, and link text.
+    Link(String, Option<String>, Vec<Inline>),
+    RawHtml,
+    SoftBreak,
+    StrongEmphasis(Vec<Inline>),
     TextualContent,
 }
 
+#[derive(Debug, Eq, PartialEq)]
 pub struct Inline {
     pub category: InlineCategory,
     pub text: String,
+    pub span: Span,
 }
 
 impl Inline {
-    fn new(category: InlineCategory, text: String) -> Inline {
-        Inline { category, text }
+    pub fn new(category: InlineCategory, text: String) -> Inline {
+        Inline {
+            category,
+            text,
+            span: Span::default(),
+        }
+    }
+
+    pub(crate) fn with_span(mut self, span: Span) -> Inline {
+        self.span = span;
+        self
+    }
+
+    // Replaces the span of the inline, and those of the inlines it contains.
+    pub(crate) fn map_spans(&mut self, map: &impl Fn(Span) -> Span) {
+        self.span = map(self.span);
+
+        if let InlineCategory::Emphasis(children)
+        | InlineCategory::Image(_, _, children)
+        | InlineCategory::Link(_, _, children)
+        | InlineCategory::StrongEmphasis(children) = &mut self.category
+        {
+            for child in children {
+                child.map_spans(map);
+            }
+        }
+    }
+
+    // Tries to turn the inlines following the closest link opener into a link or an image, when
+    // the input starts with a link closer. The source is the whole text being parsed, which the
+    // input is a suffix of.
+    fn try_parse_link_closer(
+        input: String,
+        source: &str,
+        document: &Document,
+        inlines: &mut Vec<Inline>,
+        delimiter_stack: &mut DelimiterStack,
+        bracket_stack: &mut BracketStack,
+    ) -> TryParseResult<()> {
+        if !input.starts_with(LINK_CLOSER) {
+            return TryParseResult::Rejected(input);
+        }
+
+        let opener = match bracket_stack.pop_active() {
+            Some(opener) => opener,
+            None => return TryParseResult::Rejected(input),
+        };
+
+        let link_text = &source[opener.text_offset..source.len() - input.len()];
+        let after_closer = String::from(&input[LINK_CLOSER.len_utf8()..]);
+
+        let after_closer = match InlineLink::try_parse(after_closer, document) {
+            TryParseResult::Accepted(parse_result) => TryParseResult::Accepted(parse_result),
+            TryParseResult::Rejected(rejected_from_inline_link) => {
+                ReferenceLink::try_resolve(link_text, rejected_from_inline_link, document)
+            }
+        };
+
+        let ((destination, optional_title), optional_leftover) = match after_closer {
+            TryParseResult::Accepted(ParseResult(matched_link, optional_leftover)) => {
+                (matched_link, optional_leftover)
+            }
+            TryParseResult::Rejected(_) => return TryParseResult::Rejected(input),
+        };
+
+        delimiter_stack.process_emphasis(inlines, opener.delimiter_stack_bottom);
+
+        let span = Span::from_offsets(
+            inlines[opener.index].span.start.offset,
+            source.len() - optional_leftover.as_ref().map_or(0, String::len),
+        );
+        let children = inlines.drain(opener.index + 1..).collect();
+        inlines.truncate(opener.index);
+
+        let category = match opener.is_image {
+            true => InlineCategory::Image(destination, optional_title, children),
+            false => {
+                bracket_stack.deactivate_link_openers();
+                InlineCategory::Link(destination, optional_title, children)
+            }
+        };
+
+        inlines.push(Inline::new(category, String::new()).with_span(span));
+
+        TryParseResult::Accepted(ParseResult((), optional_leftover))
     }
 }
 
 impl Parse<Inline> for Inline {
     fn parse(input: String, document: &Document) -> ParseResult<Inline> {
-        match CodeSpan::try_parse(input, document) {
+        let input = match LineBreak::try_parse(input, document) {
+            TryParseResult::Accepted(ParseResult(matched_line_break, leftover_from_line_break)) => {
+                return ParseResult(
+                    Inline::new(matched_line_break, String::new()),
+                    leftover_from_line_break,
+                )
+            }
+            TryParseResult::Rejected(rejected_from_line_break) => rejected_from_line_break,
+        };
+
+        let input = match BackslashEscape::try_parse(input, document) {
+            TryParseResult::Accepted(ParseResult(
+                matched_escaped_character,
+                leftover_from_backslash_escape,
+            )) => {
+                return ParseResult(
+                    Inline::new(InlineCategory::TextualContent, matched_escaped_character),
+                    leftover_from_backslash_escape,
+                )
+            }
+            TryParseResult::Rejected(rejected_from_backslash_escape) => {
+                rejected_from_backslash_escape
+            }
+        };
+
+        let input = match CharacterReference::try_parse(input, document) {
+            TryParseResult::Accepted(ParseResult(
+                matched_character_reference,
+                leftover_from_character_reference,
+            )) => {
+                return ParseResult(
+                    Inline::new(InlineCategory::TextualContent, matched_character_reference),
+                    leftover_from_character_reference,
+                )
+            }
+            TryParseResult::Rejected(rejected_from_character_reference) => {
+                rejected_from_character_reference
+            }
+        };
+
+        let input = match CodeSpan::try_parse(input, document) {
             TryParseResult::Accepted(ParseResult(matched_code_span, leftover_from_code_span)) => {
-                ParseResult(
+                return ParseResult(
                     Inline::new(InlineCategory::CodeSpan, matched_code_span),
                     leftover_from_code_span,
                 )
             }
-            TryParseResult::Rejected(rejected_from_code_span_matcher) => ParseResult(
-                Inline::new(
-                    InlineCategory::TextualContent,
-                    rejected_from_code_span_matcher,
-                ),
-                None,
-            ),
-        }
+            TryParseResult::Rejected(rejected_from_code_span_matcher) => {
+                rejected_from_code_span_matcher
+            }
+        };
+
+        let input_length = input.len();
+
+        let input = match Autolink::try_parse(input, document) {
+            TryParseResult::Accepted(ParseResult(
+                (matched_destination, matched_link_text),
+                leftover_from_autolink,
+            )) => {
+                // The link text is the autolink without its angle brackets. Like any span of an
+                // inline returned here, it is relative to the start of the input.
+                let autolink_length =
+                    input_length - leftover_from_autolink.as_ref().map_or(0, String::len);
+                let link_text = Inline::new(InlineCategory::TextualContent, matched_link_text)
+                    .with_span(Span::from_offsets(1, autolink_length - 1));
+
+                return ParseResult(
+                    Inline::new(
+                        InlineCategory::Link(matched_destination, None, vec![link_text]),
+                        String::new(),
+                    ),
+                    leftover_from_autolink,
+                );
+            }
+            TryParseResult::Rejected(rejected_from_autolink) => rejected_from_autolink,
+        };
+
+        let input = match RawHtml::try_parse(input, document) {
+            TryParseResult::Accepted(ParseResult(matched_raw_html, leftover_from_raw_html)) => {
+                return ParseResult(
+                    Inline::new(InlineCategory::RawHtml, matched_raw_html),
+                    leftover_from_raw_html,
+                )
+            }
+            TryParseResult::Rejected(rejected_from_raw_html) => rejected_from_raw_html,
+        };
+
+        let ParseResult(matched_textual_content, leftover_from_textual_content) =
+            TextualContent::parse(input, document);
+
+        ParseResult(
+            Inline::new(InlineCategory::TextualContent, matched_textual_content),
+            leftover_from_textual_content,
+        )
     }
 }
 
+// Delimiter runs and link openers are parsed as textual content first, and only turned into
+// emphasis, links and images once it is known how they match up with what follows them. The spans
+// of the inlines are relative to the input, and only hold offsets.
 impl ParseMultiple<Inline> for Inline {
     fn parse_multiple(input: String, document: &Document) -> Vec<Inline> {
         let mut inlines = Vec::new();
-        let mut optional_unconsumed = Some(input);
+        let mut delimiter_stack = DelimiterStack::default();
+        let mut bracket_stack = BracketStack::default();
+        let backtick_strings = BacktickStrings::index(&input);
 
-        loop {
-            if let Some(unconsumed) = optional_unconsumed {
-                let ParseResult(inline, optional_leftover) = Inline::parse(unconsumed, document);
+        let source = input;
+        let mut previous_character = None;
+        let mut unconsumed = source.clone();
 
-                inlines.push(inline);
-                optional_unconsumed = optional_leftover;
-            } else {
-                break;
-            }
+        while !unconsumed.is_empty() {
+            let input = unconsumed.clone();
+
+            let input = match DelimiterRun::try_parse(input, document) {
+                TryParseResult::Accepted(ParseResult(
+                    matched_delimiter_run,
+                    optional_leftover_from_delimiter_run,
+                )) => {
+                    let leftover = optional_leftover_from_delimiter_run.unwrap_or_default();
+
+                    delimiter_stack.push(
+                        &matched_delimiter_run,
+                        inlines.len(),
+                        previous_character,
+                        leftover.chars().next(),
+                    );
+
+                    let span = Span::from_offsets(
+                        source.len() - unconsumed.len(),
+                        source.len() - leftover.len(),
+                    );
+
+                    previous_character = matched_delimiter_run.chars().last();
+                    inlines.push(
+                        Inline::new(InlineCategory::TextualContent, matched_delimiter_run)
+                            .with_span(span),
+                    );
+                    unconsumed = leftover;
+                    continue;
+                }
+                TryParseResult::Rejected(rejected_from_delimiter_run) => {
+                    rejected_from_delimiter_run
+                }
+            };
+
+            let input = match LinkOpener::try_parse(input, document) {
+                TryParseResult::Accepted(ParseResult(
+                    matched_link_opener,
+                    optional_leftover_from_link_opener,
+                )) => {
+                    let start = source.len() - unconsumed.len();
+                    unconsumed = optional_leftover_from_link_opener.unwrap_or_default();
+
+                    bracket_stack.push(
+                        &matched_link_opener,
+                        inlines.len(),
+                        source.len() - unconsumed.len(),
+                        delimiter_stack.len(),
+                    );
+
+                    previous_character = matched_link_opener.chars().last();
+                    inlines.push(
+                        Inline::new(InlineCategory::TextualContent, matched_link_opener)
+                            .with_span(Span::from_offsets(start, source.len() - unconsumed.len())),
+                    );
+                    continue;
+                }
+                TryParseResult::Rejected(rejected_from_link_opener) => rejected_from_link_opener,
+            };
+
+            let input = match Inline::try_parse_link_closer(
+                input,
+                &source,
+                document,
+                &mut inlines,
+                &mut delimiter_stack,
+                &mut bracket_stack,
+            ) {
+                TryParseResult::Accepted(ParseResult(_, optional_leftover_from_link_closer)) => {
+                    let leftover = optional_leftover_from_link_closer.unwrap_or_default();
+
+                    previous_character = unconsumed[..unconsumed.len() - leftover.len()]
+                        .chars()
+                        .last();
+                    unconsumed = leftover;
+                    continue;
+                }
+                TryParseResult::Rejected(rejected_from_link_closer) => rejected_from_link_closer,
+            };
+
+            let start = source.len() - unconsumed.len();
+            let ParseResult(mut inline, optional_leftover) =
+                match backtick_strings.is_unclosed_at(&input, start) {
+                    true => {
+                        let ParseResult(matched_textual_content, leftover_from_textual_content) =
+                            TextualContent::parse(input, document);
+
+                        ParseResult(
+                            Inline::new(InlineCategory::TextualContent, matched_textual_content),
+                            leftover_from_textual_content,
+                        )
+                    }
+                    false => Inline::parse(input, document),
+                };
+            let leftover = optional_leftover.unwrap_or_default();
+
+            inline.map_spans(&|span: Span| {
+                Span::from_offsets(start + span.start.offset, start + span.end.offset)
+            });
+            inline.span = Span::from_offsets(start, source.len() - leftover.len());
+
+            previous_character = unconsumed[..unconsumed.len() - leftover.len()]
+                .chars()
+                .last();
+            inlines.push(inline);
+            unconsumed = leftover;
         }
 
+        delimiter_stack.process_emphasis(&mut inlines, 0);
+
         inlines
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Document, Inline, InlineCategory, ParseMultiple, Span};
+    use crate::document::parsers::block::leaf::link_reference_definition::LinkReference;
+
+    // Most tests are about what the inlines are, rather than where they are.
+    fn parse(input: String, document: &Document) -> Vec<Inline> {
+        let mut inlines = Inline::parse_multiple(input, document);
+
+        for inline in &mut inlines {
+            inline.map_spans(&|_| Span::default());
+        }
+
+        inlines
+    }
+
+    fn text(text: &str) -> Inline {
+        Inline::new(InlineCategory::TextualContent, String::from(text))
+    }
+
+    fn emphasis(children: Vec<Inline>) -> Inline {
+        Inline::new(InlineCategory::Emphasis(children), String::new())
+    }
+
+    fn strong_emphasis(children: Vec<Inline>) -> Inline {
+        Inline::new(InlineCategory::StrongEmphasis(children), String::new())
+    }
+
+    #[test]
+    fn it_parses_emphasis_and_strong_emphasis() {
+        let document = Document::new();
+
+        assert_eq!(
+            parse(String::from("*foo* __bar__"), &document),
+            vec![
+                emphasis(vec![text("foo")]),
+                text(" "),
+                strong_emphasis(vec![text("bar")]),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_parses_nested_emphasis() {
+        let document = Document::new();
+
+        assert_eq!(
+            parse(String::from("***a** b*"), &document),
+            vec![emphasis(vec![strong_emphasis(vec![text("a")]), text(" b")])]
+        );
+
+        assert_eq!(
+            parse(String::from("***foo***"), &document),
+            vec![emphasis(vec![strong_emphasis(vec![text("foo")])])]
+        );
+    }
+
+    #[test]
+    fn it_does_not_open_emphasis_with_delimiter_followed_by_whitespace() {
+        let document = Document::new();
+
+        assert_eq!(
+            parse(String::from("a * foo bar*"), &document),
+            vec![text("a "), text("*"), text(" foo bar"), text("*")]
+        );
+    }
+
+    #[test]
+    fn it_does_not_use_underscores_inside_words() {
+        let document = Document::new();
+
+        assert_eq!(
+            parse(String::from("snake_case_name *in*word"), &document),
+            vec![
+                text("snake"),
+                text("_"),
+                text("case"),
+                text("_"),
+                text("name "),
+                emphasis(vec![text("in")]),
+                text("word"),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_applies_multiple_of_three_rule() {
+        let document = Document::new();
+
+        assert_eq!(
+            parse(String::from("*foo**bar**baz*"), &document),
+            vec![emphasis(vec![
+                text("foo"),
+                strong_emphasis(vec![text("bar")]),
+                text("baz"),
+            ])]
+        );
+
+        assert_eq!(
+            parse(String::from("*foo**bar*"), &document),
+            vec![emphasis(vec![text("foo"), text("**"), text("bar")])]
+        );
+    }
+
+    #[test]
+    fn it_leaves_unmatched_delimiters_as_text() {
+        let document = Document::new();
+
+        assert_eq!(
+            parse(String::from("**foo*"), &document),
+            vec![text("*"), emphasis(vec![text("foo")])]
+        );
+    }
+
+    #[test]
+    fn it_parses_code_spans_between_text() {
+        let document = Document::new();
+
+        assert_eq!(
+            parse(String::from("foo `*bar*` baz"), &document),
+            vec![
+                text("foo "),
+                Inline::new(InlineCategory::CodeSpan, String::from("*bar*")),
+                text(" baz"),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_parses_links_and_images() {
+        let document = Document::new();
+
+        assert_eq!(
+            parse(
+                String::from("[*foo*](/uri \"title\") ![bar](/bar)"),
+                &document
+            ),
+            vec![
+                Inline::new(
+                    InlineCategory::Link(
+                        String::from("/uri"),
+                        Some(String::from("title")),
+                        vec![emphasis(vec![text("foo")])]
+                    ),
+                    String::new()
+                ),
+                text(" "),
+                Inline::new(
+                    InlineCategory::Image(String::from("/bar"), None, vec![text("bar")]),
+                    String::new()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_does_not_nest_links() {
+        let document = Document::new();
+
+        assert_eq!(
+            parse(String::from("[a [b](c) d](e)"), &document),
+            vec![
+                text("["),
+                text("a "),
+                Inline::new(
+                    InlineCategory::Link(String::from("c"), None, vec![text("b")]),
+                    String::new()
+                ),
+                text(" d"),
+                text("](e)"),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_keeps_emphasis_from_crossing_link_text() {
+        let document = Document::new();
+
+        assert_eq!(
+            parse(String::from("*[foo*](url)"), &document),
+            vec![
+                text("*"),
+                Inline::new(
+                    InlineCategory::Link(String::from("url"), None, vec![text("foo"), text("*")]),
+                    String::new()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_leaves_brackets_without_destination_as_text() {
+        let document = Document::new();
+
+        assert_eq!(
+            parse(String::from("[foo] (bar)"), &document),
+            vec![text("["), text("foo"), text("] (bar)")]
+        );
+    }
+
+    #[test]
+    fn it_resolves_full_collapsed_and_shortcut_references() {
+        let mut document = Document::new();
+
+        document.link_reference_map.insert(
+            String::from("FOO BAR"),
+            LinkReference {
+                destination: String::from("/url"),
+                title: Some(String::from("title")),
+            },
+        );
+
+        let link = |children| {
+            Inline::new(
+                InlineCategory::Link(String::from("/url"), Some(String::from("title")), children),
+                String::new(),
+            )
+        };
+
+        assert_eq!(
+            parse(
+                String::from("[baz][Foo  Bar] [foo bar][] [Foo\nbar]"),
+                &document
+            ),
+            vec![
+                link(vec![text("baz")]),
+                text(" "),
+                link(vec![text("foo bar")]),
+                text(" "),
+                link(vec![
+                    text("Foo"),
+                    Inline::new(InlineCategory::SoftBreak, String::new()),
+                    text("bar")
+                ]),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_leaves_unresolved_references_as_text() {
+        let document = Document::new();
+
+        assert_eq!(
+            parse(String::from("[foo][bar] [baz]"), &document),
+            vec![
+                text("["),
+                text("foo"),
+                text("]"),
+                text("["),
+                text("bar"),
+                text("] "),
+                text("["),
+                text("baz"),
+                text("]"),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_decodes_backslash_escapes_and_character_references() {
+        let document = Document::new();
+
+        assert_eq!(
+            parse(
+                String::from("\\*foo\\* &amp; &#35;\\a `&amp; \\*`"),
+                &document
+            ),
+            vec![
+                text("*"),
+                text("foo"),
+                text("*"),
+                text(" "),
+                text("&"),
+                text(" "),
+                text("#"),
+                text("\\a "),
+                Inline::new(InlineCategory::CodeSpan, String::from("&amp; \\*")),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_parses_autolinks() {
+        let document = Document::new();
+
+        assert_eq!(
+            parse(
+                String::from("<https://example.com> and <foo@bar.example.com>"),
+                &document
+            ),
+            vec![
+                Inline::new(
+                    InlineCategory::Link(
+                        String::from("https://example.com"),
+                        None,
+                        vec![text("https://example.com")]
+                    ),
+                    String::new()
+                ),
+                text(" and "),
+                Inline::new(
+                    InlineCategory::Link(
+                        String::from("mailto:foo@bar.example.com"),
+                        None,
+                        vec![text("foo@bar.example.com")]
+                    ),
+                    String::new()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_parses_raw_html() {
+        let document = Document::new();
+
+        assert_eq!(
+            parse(String::from("*<a href=\"*\">* <!-- -->"), &document),
+            vec![
+                emphasis(vec![Inline::new(
+                    InlineCategory::RawHtml,
+                    String::from("<a href=\"*\">")
+                )]),
+                text(" "),
+                Inline::new(InlineCategory::RawHtml, String::from("<!-- -->")),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_gives_autolinks_and_raw_html_precedence_over_link_closers() {
+        let document = Document::new();
+
+        assert_eq!(
+            parse(String::from("[foo <bar attr=\"](baz)\">"), &document),
+            vec![
+                text("["),
+                text("foo "),
+                Inline::new(
+                    InlineCategory::RawHtml,
+                    String::from("<bar attr=\"](baz)\">")
+                ),
+            ]
+        );
+
+        assert_eq!(
+            parse(String::from("[foo<https://a.b/?q=](uri)>"), &document),
+            vec![
+                text("["),
+                text("foo"),
+                Inline::new(
+                    InlineCategory::Link(
+                        String::from("https://a.b/?q=](uri)"),
+                        None,
+                        vec![text("https://a.b/?q=](uri)")]
+                    ),
+                    String::new()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_leaves_invalid_autolinks_and_raw_html_as_text() {
+        let document = Document::new();
+
+        assert_eq!(
+            parse(String::from("< https://a.b > <33>"), &document),
+            vec![text("< https://a.b > "), text("<33>")]
+        );
+    }
+
+    #[test]
+    fn it_parses_hard_and_soft_line_breaks() {
+        let document = Document::new();
+
+        assert_eq!(
+            parse(String::from("foo  \nbar\\\r\nbaz \rqux"), &document),
+            vec![
+                text("foo"),
+                Inline::new(InlineCategory::HardBreak, String::new()),
+                text("bar"),
+                Inline::new(InlineCategory::HardBreak, String::new()),
+                text("baz"),
+                Inline::new(InlineCategory::SoftBreak, String::new()),
+                text("qux"),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_locates_inlines_in_the_input() {
+        let document = Document::new();
+
+        let inlines =
+            Inline::parse_multiple(String::from("a *b* [c](/u) <http://x> **d*"), &document);
+
+        assert_eq!(
+            inlines
+                .iter()
+                .map(|inline| inline.span)
+                .collect::<Vec<Span>>(),
+            vec![
+                Span::from_offsets(0, 2),
+                Span::from_offsets(2, 5),
+                Span::from_offsets(5, 6),
+                Span::from_offsets(6, 13),
+                Span::from_offsets(13, 14),
+                Span::from_offsets(14, 24),
+                Span::from_offsets(24, 25),
+                Span::from_offsets(25, 26),
+                Span::from_offsets(26, 29),
+            ]
+        );
+
+        let InlineCategory::Link(_, _, link_text) = &inlines[3].category else {
+            panic!("expected a link, got {:?}", inlines[3]);
+        };
+        assert_eq!(link_text[0].span, Span::from_offsets(7, 8));
+
+        let InlineCategory::Link(_, _, autolink_text) = &inlines[5].category else {
+            panic!("expected an autolink, got {:?}", inlines[5]);
+        };
+        assert_eq!(autolink_text[0].span, Span::from_offsets(15, 23));
+    }
+}
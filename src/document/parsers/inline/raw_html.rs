@@ -0,0 +1,179 @@
+use super::{Document, HtmlTag, ParseResult, TryParse, TryParseResult};
+
+const COMMENT_OPENER: &str = "<!--";
+const COMMENT_CLOSER: &str = "-->";
+const PROCESSING_INSTRUCTION_OPENER: &str = "<?";
+const PROCESSING_INSTRUCTION_CLOSER: &str = "?>";
+const DECLARATION_OPENER: &str = "<!";
+const DECLARATION_CLOSER: char = '>';
+const CDATA_SECTION_OPENER: &str = "<![CDATA[";
+const CDATA_SECTION_CLOSER: &str = "]]>";
+
+// [SPEC]: https://spec.commonmark.org/0.30/#raw-html
+pub struct RawHtml;
+
+impl RawHtml {
+    // Like the tag scanners, these return how many bytes at the start of the text they match.
+
+    // [SPEC]: https://spec.commonmark.org/0.30/#html-comment
+    fn scan_comment(text: &str) -> Option<usize> {
+        let content = text.strip_prefix(COMMENT_OPENER)?;
+
+        if content.starts_with('>') || content.starts_with("->") {
+            return None;
+        }
+
+        let content_length = content.find("--")?;
+
+        match content[content_length..].starts_with(COMMENT_CLOSER)
+            && !content[..content_length].ends_with('-')
+        {
+            true => Some(COMMENT_OPENER.len() + content_length + COMMENT_CLOSER.len()),
+            false => None,
+        }
+    }
+
+    // [SPEC]: https://spec.commonmark.org/0.30/#processing-instruction
+    fn scan_processing_instruction(text: &str) -> Option<usize> {
+        let content = text.strip_prefix(PROCESSING_INSTRUCTION_OPENER)?;
+
+        content
+            .find(PROCESSING_INSTRUCTION_CLOSER)
+            .map(|content_length| {
+                PROCESSING_INSTRUCTION_OPENER.len()
+                    + content_length
+                    + PROCESSING_INSTRUCTION_CLOSER.len()
+            })
+    }
+
+    // [SPEC]: https://spec.commonmark.org/0.30/#declaration
+    fn scan_declaration(text: &str) -> Option<usize> {
+        let content = text.strip_prefix(DECLARATION_OPENER)?;
+
+        if !content.starts_with(|character: char| character.is_ascii_alphabetic()) {
+            return None;
+        }
+
+        content.find(DECLARATION_CLOSER).map(|content_length| {
+            DECLARATION_OPENER.len() + content_length + DECLARATION_CLOSER.len_utf8()
+        })
+    }
+
+    // [SPEC]: https://spec.commonmark.org/0.30/#cdata-section
+    fn scan_cdata_section(text: &str) -> Option<usize> {
+        let content = text.strip_prefix(CDATA_SECTION_OPENER)?;
+
+        content.find(CDATA_SECTION_CLOSER).map(|content_length| {
+            CDATA_SECTION_OPENER.len() + content_length + CDATA_SECTION_CLOSER.len()
+        })
+    }
+}
+
+// Accepts an open tag, closing tag, comment, processing instruction, declaration or CDATA section,
+// and returns it as is, since raw HTML is passed through without any decoding.
+impl TryParse<String> for RawHtml {
+    fn try_parse(input: String, document: &Document) -> TryParseResult<String> {
+        let input = match HtmlTag::try_parse(input, document) {
+            TryParseResult::Accepted(parse_result) => {
+                return TryParseResult::Accepted(parse_result)
+            }
+            TryParseResult::Rejected(rejected_from_html_tag) => rejected_from_html_tag,
+        };
+
+        match RawHtml::scan_comment(&input)
+            .or_else(|| RawHtml::scan_processing_instruction(&input))
+            .or_else(|| RawHtml::scan_cdata_section(&input))
+            .or_else(|| RawHtml::scan_declaration(&input))
+        {
+            Some(length) => TryParseResult::Accepted(ParseResult::new(
+                String::from(&input[..length]),
+                String::from(&input[length..]),
+            )),
+            None => TryParseResult::Rejected(input),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Document, ParseResult, RawHtml, TryParse, TryParseResult};
+
+    #[test]
+    fn it_accepts_tags() {
+        let document = Document::new();
+
+        assert_eq!(
+            RawHtml::try_parse(String::from("<a href=\"*\">*"), &document),
+            TryParseResult::Accepted(ParseResult(
+                String::from("<a href=\"*\">"),
+                Some(String::from("*"))
+            ))
+        );
+
+        assert_eq!(
+            RawHtml::try_parse(String::from("</a>"), &document),
+            TryParseResult::Accepted(ParseResult(String::from("</a>"), None))
+        );
+    }
+
+    #[test]
+    fn it_accepts_comments() {
+        let document = Document::new();
+
+        assert_eq!(
+            RawHtml::try_parse(
+                String::from("<!-- this is a\ncomment - with hyphen --> bar"),
+                &document
+            ),
+            TryParseResult::Accepted(ParseResult(
+                String::from("<!-- this is a\ncomment - with hyphen -->"),
+                Some(String::from(" bar"))
+            ))
+        );
+    }
+
+    #[test]
+    fn it_rejects_malformed_comments() {
+        let document = Document::new();
+
+        for input in [
+            "<!-- not a comment -- 2 -->",
+            "<!-->",
+            "<!--->",
+            "<!-- foo--->",
+        ] {
+            assert_eq!(
+                RawHtml::try_parse(String::from(input), &document),
+                TryParseResult::Rejected(String::from(input))
+            );
+        }
+    }
+
+    #[test]
+    fn it_accepts_processing_instructions_declarations_and_cdata_sections() {
+        let document = Document::new();
+
+        for input in [
+            "<?php echo $a; ?>",
+            "<!ELEMENT br EMPTY>",
+            "<![CDATA[>&<]]>",
+        ] {
+            assert_eq!(
+                RawHtml::try_parse(String::from(input), &document),
+                TryParseResult::Accepted(ParseResult(String::from(input), None))
+            );
+        }
+    }
+
+    #[test]
+    fn it_rejects_unterminated_constructs() {
+        let document = Document::new();
+
+        for input in ["<?php", "<!ELEMENT", "<![CDATA[foo]>", "<!1>", "<a"] {
+            assert_eq!(
+                RawHtml::try_parse(String::from(input), &document),
+                TryParseResult::Rejected(String::from(input))
+            );
+        }
+    }
+}
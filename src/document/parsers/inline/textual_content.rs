@@ -0,0 +1,90 @@
+use super::{Document, Parse, ParseResult};
+use crate::document::preliminaries::{is_line_ending, CARRIAGE_RETURN, NEWLINE, SPACE};
+
+const CODE_SPAN_DELIMITER: char = '`';
+
+// Characters that may start an inline other than textual content.
+const SPECIAL_CHARACTERS: [char; 11] = [
+    '`',
+    '*',
+    '_',
+    '[',
+    ']',
+    '!',
+    '\\',
+    '&',
+    '<',
+    NEWLINE,
+    CARRIAGE_RETURN,
+];
+
+// [SPEC]: https://spec.commonmark.org/0.30/#textual-content
+pub struct TextualContent;
+
+// Consumes at least one character, and stops before the next one that may start another kind of
+// inline. A run of backticks that didn't open a code span is consumed as a whole, so that none of
+// its backticks can open one either. Spaces right before a line ending are left for the line
+// break.
+impl Parse<String> for TextualContent {
+    fn parse(input: String, _document: &Document) -> ParseResult<String> {
+        let length = match input.chars().next() {
+            Some(CODE_SPAN_DELIMITER) => {
+                input.len() - input.trim_start_matches(CODE_SPAN_DELIMITER).len()
+            }
+            Some(first_character) => {
+                let length = input[first_character.len_utf8()..]
+                    .find(SPECIAL_CHARACTERS)
+                    .map_or(input.len(), |index| index + first_character.len_utf8());
+
+                match input[length..].starts_with(is_line_ending) {
+                    true => input[..length]
+                        .trim_end_matches(SPACE)
+                        .len()
+                        .max(first_character.len_utf8()),
+                    false => length,
+                }
+            }
+            None => 0,
+        };
+
+        ParseResult::new(
+            String::from(&input[..length]),
+            String::from(&input[length..]),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Document, Parse, ParseResult, TextualContent};
+
+    #[test]
+    fn it_stops_before_special_characters() {
+        let document = Document::new();
+
+        assert_eq!(
+            TextualContent::parse(String::from("*foo *bar*"), &document),
+            ParseResult(String::from("*foo "), Some(String::from("*bar*")))
+        );
+    }
+
+    #[test]
+    fn it_consumes_whole_backtick_run() {
+        let document = Document::new();
+
+        assert_eq!(
+            TextualContent::parse(String::from("``foo`"), &document),
+            ParseResult(String::from("``"), Some(String::from("foo`")))
+        );
+    }
+
+    #[test]
+    fn it_stops_before_spaces_preceding_line_ending() {
+        let document = Document::new();
+
+        assert_eq!(
+            TextualContent::parse(String::from("foo  \r\nbar"), &document),
+            ParseResult(String::from("foo"), Some(String::from("  \r\nbar")))
+        );
+    }
+}
@@ -0,0 +1,59 @@
+use super::{Document, ParseResult, TryParse, TryParseResult};
+
+const BACKSLASH: char = '\\';
+
+// [SPEC]: https://spec.commonmark.org/0.30/#backslash-escapes
+pub struct BackslashEscape;
+
+// Accepts a backslash followed by ASCII punctuation, and returns the punctuation character. Any
+// other backslash is literal.
+impl TryParse<String> for BackslashEscape {
+    fn try_parse(input: String, _document: &Document) -> TryParseResult<String> {
+        match input.strip_prefix(BACKSLASH).and_then(|after_backslash| {
+            after_backslash
+                .chars()
+                .next()
+                .filter(char::is_ascii_punctuation)
+        }) {
+            Some(escaped_character) => {
+                let length = BACKSLASH.len_utf8() + escaped_character.len_utf8();
+
+                TryParseResult::Accepted(ParseResult::new(
+                    String::from(escaped_character),
+                    String::from(&input[length..]),
+                ))
+            }
+            None => TryParseResult::Rejected(input),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BackslashEscape, Document, ParseResult, TryParse, TryParseResult};
+
+    #[test]
+    fn it_accepts_escaped_ascii_punctuation() {
+        let document = Document::new();
+
+        assert_eq!(
+            BackslashEscape::try_parse(String::from("\\*not emphasized*"), &document),
+            TryParseResult::Accepted(ParseResult(
+                String::from("*"),
+                Some(String::from("not emphasized*"))
+            ))
+        );
+    }
+
+    #[test]
+    fn it_rejects_other_characters() {
+        let document = Document::new();
+
+        for input in ["\\a", "\\→", "\\ ", "\\"] {
+            assert_eq!(
+                BackslashEscape::try_parse(String::from(input), &document),
+                TryParseResult::Rejected(String::from(input))
+            );
+        }
+    }
+}
@@ -0,0 +1,143 @@
+use super::{entities::ENTITIES, Document, ParseResult, TryParse, TryParseResult};
+
+const REFERENCE_OPENER: char = '&';
+const REFERENCE_CLOSER: char = ';';
+const NUMERIC_REFERENCE_MARKER: char = '#';
+const MAXIMUM_DECIMAL_DIGIT_COUNT: usize = 7;
+const MAXIMUM_HEXADECIMAL_DIGIT_COUNT: usize = 6;
+
+const REPLACEMENT_CHARACTER: char = '\u{FFFD}';
+
+// [SPEC]: https://spec.commonmark.org/0.30/#entity-and-numeric-character-references
+pub struct CharacterReference;
+
+impl CharacterReference {
+    // NUL, surrogates and code points beyond Unicode are replaced, rather than rejected.
+    fn decode_numeric(digits: &str, radix: u32) -> String {
+        let character = u32::from_str_radix(digits, radix)
+            .ok()
+            .filter(|code_point| *code_point != 0)
+            .and_then(char::from_u32)
+            .unwrap_or(REPLACEMENT_CHARACTER);
+
+        String::from(character)
+    }
+}
+
+// Accepts a named, decimal or hexadecimal reference, and returns the characters it stands for.
+impl TryParse<String> for CharacterReference {
+    fn try_parse(input: String, _document: &Document) -> TryParseResult<String> {
+        let reference = match input
+            .strip_prefix(REFERENCE_OPENER)
+            .and_then(|after_opener| after_opener.split_once(REFERENCE_CLOSER))
+        {
+            Some((reference, _)) => reference,
+            None => return TryParseResult::Rejected(input),
+        };
+
+        let optional_decoded = match reference.strip_prefix(NUMERIC_REFERENCE_MARKER) {
+            Some(number) => match number
+                .strip_prefix('x')
+                .or_else(|| number.strip_prefix('X'))
+            {
+                Some(digits)
+                    if (1..=MAXIMUM_HEXADECIMAL_DIGIT_COUNT).contains(&digits.len())
+                        && digits
+                            .chars()
+                            .all(|character| character.is_ascii_hexdigit()) =>
+                {
+                    Some(CharacterReference::decode_numeric(digits, 16))
+                }
+                Some(_) => None,
+                None if (1..=MAXIMUM_DECIMAL_DIGIT_COUNT).contains(&number.len())
+                    && number.chars().all(|character| character.is_ascii_digit()) =>
+                {
+                    Some(CharacterReference::decode_numeric(number, 10))
+                }
+                None => None,
+            },
+            None => ENTITIES
+                .binary_search_by(|(name, _)| (*name).cmp(reference))
+                .ok()
+                .map(|index| String::from(ENTITIES[index].1)),
+        };
+
+        match optional_decoded {
+            Some(decoded) => {
+                let length =
+                    REFERENCE_OPENER.len_utf8() + reference.len() + REFERENCE_CLOSER.len_utf8();
+
+                TryParseResult::Accepted(ParseResult::new(decoded, String::from(&input[length..])))
+            }
+            None => TryParseResult::Rejected(input),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CharacterReference, Document, ParseResult, TryParse, TryParseResult};
+
+    #[test]
+    fn it_decodes_named_references() {
+        let document = Document::new();
+
+        assert_eq!(
+            CharacterReference::try_parse(String::from("&copy; 2024"), &document),
+            TryParseResult::Accepted(ParseResult(String::from("©"), Some(String::from(" 2024"))))
+        );
+
+        assert_eq!(
+            CharacterReference::try_parse(String::from("&ngE;"), &document),
+            TryParseResult::Accepted(ParseResult(String::from("\u{2267}\u{338}"), None))
+        );
+    }
+
+    #[test]
+    fn it_decodes_decimal_and_hexadecimal_references() {
+        let document = Document::new();
+
+        assert_eq!(
+            CharacterReference::try_parse(String::from("&#35;"), &document),
+            TryParseResult::Accepted(ParseResult(String::from("#"), None))
+        );
+
+        assert_eq!(
+            CharacterReference::try_parse(String::from("&#X22;"), &document),
+            TryParseResult::Accepted(ParseResult(String::from("\""), None))
+        );
+    }
+
+    #[test]
+    fn it_replaces_invalid_code_points() {
+        let document = Document::new();
+
+        for input in ["&#0;", "&#xD800;", "&#1234567;"] {
+            assert_eq!(
+                CharacterReference::try_parse(String::from(input), &document),
+                TryParseResult::Accepted(ParseResult(String::from("\u{FFFD}"), None))
+            );
+        }
+    }
+
+    #[test]
+    fn it_rejects_invalid_references() {
+        let document = Document::new();
+
+        for input in [
+            "&nbsp",
+            "&x;",
+            "&#;",
+            "&#x;",
+            "&#87654321;",
+            "&#abcdef0;",
+            "&ThisIsNotDefined;",
+            "& amp;",
+        ] {
+            assert_eq!(
+                CharacterReference::try_parse(String::from(input), &document),
+                TryParseResult::Rejected(String::from(input))
+            );
+        }
+    }
+}
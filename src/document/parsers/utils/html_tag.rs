@@ -0,0 +1,275 @@
+use super::{skip_whitespace, Document, ParseResult, TryParse, TryParseResult};
+use crate::document::preliminaries::{is_line_ending, is_space_or_tab};
+
+const TAG_OPENER: char = '<';
+const TAG_CLOSER: char = '>';
+const CLOSING_TAG_OPENER: &str = "</";
+const SELF_CLOSING_TAG_CLOSER: &str = "/>";
+const ATTRIBUTE_VALUE_SEPARATOR: char = '=';
+
+// [SPEC]: https://spec.commonmark.org/0.30/#open-tag
+// [SPEC]: https://spec.commonmark.org/0.30/#closing-tag
+pub struct HtmlTag;
+
+impl HtmlTag {
+    // Tag names are case-insensitive, so this is used to compare them against known names.
+    pub fn name(tag: &str) -> String {
+        let name = tag
+            .trim_start_matches(CLOSING_TAG_OPENER)
+            .trim_start_matches(TAG_OPENER);
+        let name_length = HtmlTag::scan_name(name).unwrap_or_default();
+
+        name[..name_length].to_ascii_lowercase()
+    }
+
+    // Returns the attributes of an open tag in order, with their values as written, but without
+    // quotes. Attributes without a value have none.
+    pub fn attributes(tag: &str) -> Vec<(String, Option<String>)> {
+        let mut attributes = Vec::new();
+        let mut position = match tag.get(1..).and_then(HtmlTag::scan_name) {
+            Some(name_length) => TAG_OPENER.len_utf8() + name_length,
+            None => return attributes,
+        };
+
+        loop {
+            let before_name = position + HtmlTag::scan_whitespace(&tag[position..]);
+
+            let name_length = match HtmlTag::scan_attribute_name(&tag[before_name..]) {
+                Some(name_length) if before_name > position => name_length,
+                _ => break,
+            };
+
+            let name = &tag[before_name..before_name + name_length];
+            position = before_name + name_length;
+
+            let before_separator = position + HtmlTag::scan_whitespace(&tag[position..]);
+            let mut optional_value = None;
+
+            if tag[before_separator..].starts_with(ATTRIBUTE_VALUE_SEPARATOR) {
+                let after_separator = before_separator + ATTRIBUTE_VALUE_SEPARATOR.len_utf8();
+                let before_value =
+                    after_separator + HtmlTag::scan_whitespace(&tag[after_separator..]);
+
+                match HtmlTag::scan_attribute_value(&tag[before_value..]) {
+                    Some(value_length) => {
+                        let value = &tag[before_value..before_value + value_length];
+                        let is_quoted = value.starts_with(|character| "\"'".contains(character));
+
+                        optional_value = Some(String::from(match is_quoted {
+                            true => &value[1..value.len() - 1],
+                            false => value,
+                        }));
+                        position = before_value + value_length;
+                    }
+                    None => break,
+                }
+            }
+
+            attributes.push((String::from(name), optional_value));
+        }
+
+        attributes
+    }
+
+    // The `scan_*` functions return how many bytes at the start of the text they match.
+
+    fn scan_name(text: &str) -> Option<usize> {
+        match text.chars().next() {
+            Some(character) if character.is_ascii_alphabetic() => Some(
+                text.find(|character: char| {
+                    !(character.is_ascii_alphanumeric() || character == '-')
+                })
+                .unwrap_or(text.len()),
+            ),
+            _ => None,
+        }
+    }
+
+    fn scan_whitespace(text: &str) -> usize {
+        text.len() - skip_whitespace(text).len()
+    }
+
+    fn scan_attribute_name(text: &str) -> Option<usize> {
+        match text.chars().next() {
+            Some(character)
+                if character.is_ascii_alphabetic() || character == '_' || character == ':' =>
+            {
+                Some(
+                    text.find(|character: char| {
+                        !(character.is_ascii_alphanumeric() || "_.:-".contains(character))
+                    })
+                    .unwrap_or(text.len()),
+                )
+            }
+            _ => None,
+        }
+    }
+
+    fn scan_attribute_value(text: &str) -> Option<usize> {
+        match text.chars().next() {
+            Some(quote) if quote == '"' || quote == '\'' => text[1..]
+                .find(quote)
+                .map(|closing_quote_index| closing_quote_index + 2),
+            Some(_) => {
+                let length = text
+                    .find(|character: char| {
+                        is_space_or_tab(character)
+                            || is_line_ending(character)
+                            || "\"'=<>`".contains(character)
+                    })
+                    .unwrap_or(text.len());
+
+                match length {
+                    0 => None,
+                    length => Some(length),
+                }
+            }
+            None => None,
+        }
+    }
+
+    fn scan_open_tag(text: &str) -> Option<usize> {
+        let mut position = TAG_OPENER.len_utf8() + HtmlTag::scan_name(text.get(1..)?)?;
+
+        loop {
+            let whitespace_length = HtmlTag::scan_whitespace(&text[position..]);
+
+            // Every attribute has to be preceded by whitespace.
+            let attribute_name_length = match whitespace_length {
+                0 => None,
+                _ => HtmlTag::scan_attribute_name(&text[position + whitespace_length..]),
+            };
+
+            let attribute_name_length = match attribute_name_length {
+                Some(attribute_name_length) => attribute_name_length,
+                None => {
+                    position += whitespace_length;
+                    break;
+                }
+            };
+
+            position += whitespace_length + attribute_name_length;
+
+            let before_separator = position + HtmlTag::scan_whitespace(&text[position..]);
+
+            if text[before_separator..].starts_with(ATTRIBUTE_VALUE_SEPARATOR) {
+                let after_separator = before_separator + ATTRIBUTE_VALUE_SEPARATOR.len_utf8();
+                let before_value =
+                    after_separator + HtmlTag::scan_whitespace(&text[after_separator..]);
+
+                position = before_value + HtmlTag::scan_attribute_value(&text[before_value..])?;
+            }
+        }
+
+        if text[position..].starts_with(SELF_CLOSING_TAG_CLOSER) {
+            Some(position + SELF_CLOSING_TAG_CLOSER.len())
+        } else if text[position..].starts_with(TAG_CLOSER) {
+            Some(position + TAG_CLOSER.len_utf8())
+        } else {
+            None
+        }
+    }
+
+    fn scan_closing_tag(text: &str) -> Option<usize> {
+        let mut position =
+            CLOSING_TAG_OPENER.len() + HtmlTag::scan_name(text.strip_prefix(CLOSING_TAG_OPENER)?)?;
+
+        position += HtmlTag::scan_whitespace(&text[position..]);
+
+        match text[position..].starts_with(TAG_CLOSER) {
+            true => Some(position + TAG_CLOSER.len_utf8()),
+            false => None,
+        }
+    }
+}
+
+// Accepts a complete open tag or closing tag, and returns it as is.
+impl TryParse<String> for HtmlTag {
+    fn try_parse(input: String, _document: &Document) -> TryParseResult<String> {
+        if !input.starts_with(TAG_OPENER) {
+            return TryParseResult::Rejected(input);
+        }
+
+        match HtmlTag::scan_open_tag(&input).or_else(|| HtmlTag::scan_closing_tag(&input)) {
+            Some(length) => TryParseResult::Accepted(ParseResult::new(
+                String::from(&input[..length]),
+                String::from(&input[length..]),
+            )),
+            None => TryParseResult::Rejected(input),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Document, HtmlTag, ParseResult, TryParse, TryParseResult};
+
+    #[test]
+    fn it_accepts_open_tags_with_attributes() {
+        let document = Document::new();
+
+        assert_eq!(
+            HtmlTag::try_parse(
+                String::from(
+                    "<a foo=\"bar\" bam = 'baz <em>\"</em>'\n_boolean zoop:33=zoop:33 /> leftover"
+                ),
+                &document
+            ),
+            TryParseResult::Accepted(ParseResult(
+                String::from("<a foo=\"bar\" bam = 'baz <em>\"</em>'\n_boolean zoop:33=zoop:33 />"),
+                Some(String::from(" leftover"))
+            ))
+        );
+    }
+
+    #[test]
+    fn it_accepts_closing_tags() {
+        let document = Document::new();
+
+        assert_eq!(
+            HtmlTag::try_parse(String::from("</details >"), &document),
+            TryParseResult::Accepted(ParseResult(String::from("</details >"), None))
+        );
+    }
+
+    #[test]
+    fn it_rejects_malformed_tags() {
+        let document = Document::new();
+
+        for input in [
+            "<33>",
+            "<__>",
+            "<a h*#ref=\"hi\">",
+            "<a href=\"hi'>",
+            "<a href=hi'>",
+            "<a\n\n  href=\"hi\">",
+            "<a/b>",
+            "</a href=\"foo\">",
+        ] {
+            assert_eq!(
+                HtmlTag::try_parse(String::from(input), &document),
+                TryParseResult::Rejected(String::from(input))
+            );
+        }
+    }
+
+    #[test]
+    fn it_extracts_attributes_without_quotes() {
+        assert_eq!(
+            HtmlTag::attributes("<img src=\"a.png\" alt = \"'b' c\"\nwidth=10 hidden />"),
+            vec![
+                (String::from("src"), Some(String::from("a.png"))),
+                (String::from("alt"), Some(String::from("'b' c"))),
+                (String::from("width"), Some(String::from("10"))),
+                (String::from("hidden"), None),
+            ]
+        );
+        assert_eq!(HtmlTag::attributes("</a>"), vec![]);
+    }
+
+    #[test]
+    fn it_extracts_lowercase_tag_name() {
+        assert_eq!(HtmlTag::name("<DIV class=\"foo\">"), "div");
+        assert_eq!(HtmlTag::name("</Table>"), "table");
+    }
+}
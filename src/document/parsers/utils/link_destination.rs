@@ -0,0 +1,184 @@
+use super::{unescape, Document, ParseResult, TryParse, TryParseResult};
+use crate::document::preliminaries::is_line_ending;
+
+const POINTY_BRACKET_OPENER: char = '<';
+const POINTY_BRACKET_CLOSER: char = '>';
+const PARENTHESIS_OPENER: char = '(';
+const PARENTHESIS_CLOSER: char = ')';
+const BACKSLASH: char = '\\';
+
+// [SPEC]: https://spec.commonmark.org/0.30/#link-destination
+pub struct LinkDestination;
+
+impl LinkDestination {
+    fn try_parse_pointy_bracketed(input: String) -> TryParseResult<String> {
+        let mut is_escaped = false;
+
+        for (index, character) in input.char_indices().skip(1) {
+            if is_escaped {
+                is_escaped = false;
+                continue;
+            }
+
+            match character {
+                BACKSLASH => is_escaped = true,
+                POINTY_BRACKET_CLOSER => {
+                    return TryParseResult::Accepted(ParseResult::new(
+                        String::from(&input[POINTY_BRACKET_OPENER.len_utf8()..index]),
+                        String::from(&input[index + POINTY_BRACKET_CLOSER.len_utf8()..]),
+                    ))
+                }
+                POINTY_BRACKET_OPENER => break,
+                character if is_line_ending(character) => break,
+                _ => {}
+            }
+        }
+
+        TryParseResult::Rejected(input)
+    }
+
+    fn try_parse_unbracketed(input: String) -> TryParseResult<String> {
+        let mut nesting_depth = 0;
+        let mut is_escaped = false;
+        let mut end_index = input.len();
+
+        for (index, character) in input.char_indices() {
+            if is_escaped {
+                is_escaped = false;
+                continue;
+            }
+
+            match character {
+                BACKSLASH => is_escaped = true,
+                PARENTHESIS_OPENER => nesting_depth += 1,
+                PARENTHESIS_CLOSER if nesting_depth == 0 => {
+                    end_index = index;
+                    break;
+                }
+                PARENTHESIS_CLOSER => nesting_depth -= 1,
+                character if character == ' ' || character.is_ascii_control() => {
+                    end_index = index;
+                    break;
+                }
+                _ => {}
+            }
+        }
+
+        if end_index == 0 || nesting_depth != 0 {
+            return TryParseResult::Rejected(input);
+        }
+
+        TryParseResult::Accepted(ParseResult::new(
+            String::from(&input[..end_index]),
+            String::from(&input[end_index..]),
+        ))
+    }
+}
+
+// Returns the destination without pointy brackets, and with backslash escapes and character
+// references decoded.
+impl TryParse<String> for LinkDestination {
+    fn try_parse(input: String, document: &Document) -> TryParseResult<String> {
+        let try_parse_result = if input.starts_with(POINTY_BRACKET_OPENER) {
+            LinkDestination::try_parse_pointy_bracketed(input)
+        } else {
+            LinkDestination::try_parse_unbracketed(input)
+        };
+
+        match try_parse_result {
+            TryParseResult::Accepted(ParseResult(destination, optional_leftover)) => {
+                TryParseResult::Accepted(ParseResult(
+                    unescape(&destination, document),
+                    optional_leftover,
+                ))
+            }
+            rejected => rejected,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Document, LinkDestination, ParseResult, TryParse, TryParseResult};
+
+    #[test]
+    fn it_accepts_destination_and_returns_leftover() {
+        let document = Document::new();
+
+        assert_eq!(
+            LinkDestination::try_parse(String::from("/url \"title\""), &document),
+            TryParseResult::Accepted(ParseResult(
+                String::from("/url"),
+                Some(String::from(" \"title\""))
+            ))
+        );
+    }
+
+    #[test]
+    fn it_accepts_pointy_bracketed_destination() {
+        let document = Document::new();
+
+        assert_eq!(
+            LinkDestination::try_parse(String::from("<my url>)"), &document),
+            TryParseResult::Accepted(ParseResult(String::from("my url"), Some(String::from(")"))))
+        );
+
+        assert_eq!(
+            LinkDestination::try_parse(String::from("<>"), &document),
+            TryParseResult::Accepted(ParseResult(String::new(), None))
+        );
+    }
+
+    #[test]
+    fn it_rejects_line_endings_in_pointy_bracketed_destination() {
+        let document = Document::new();
+
+        assert_eq!(
+            LinkDestination::try_parse(String::from("<foo\nbar>"), &document),
+            TryParseResult::Rejected(String::from("<foo\nbar>"))
+        );
+    }
+
+    #[test]
+    fn it_accepts_balanced_parentheses() {
+        let document = Document::new();
+
+        assert_eq!(
+            LinkDestination::try_parse(String::from("foo(and(bar)))"), &document),
+            TryParseResult::Accepted(ParseResult(
+                String::from("foo(and(bar))"),
+                Some(String::from(")"))
+            ))
+        );
+    }
+
+    #[test]
+    fn it_rejects_unbalanced_parentheses() {
+        let document = Document::new();
+
+        assert_eq!(
+            LinkDestination::try_parse(String::from("foo(and(bar)"), &document),
+            TryParseResult::Rejected(String::from("foo(and(bar)"))
+        );
+    }
+
+    #[test]
+    fn it_accepts_and_decodes_escaped_parentheses() {
+        let document = Document::new();
+
+        assert_eq!(
+            LinkDestination::try_parse(String::from("foo\\(and\\(bar\\)"), &document),
+            TryParseResult::Accepted(ParseResult(String::from("foo(and(bar)"), None))
+        );
+    }
+
+    #[test]
+    fn it_decodes_character_references() {
+        let document = Document::new();
+
+        assert_eq!(
+            LinkDestination::try_parse(String::from("<f&ouml;&ouml;\\>>"), &document),
+            TryParseResult::Accepted(ParseResult(String::from("föö>"), None))
+        );
+    }
+}
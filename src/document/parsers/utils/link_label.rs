@@ -0,0 +1,133 @@
+use super::{Document, ParseResult, TryParse, TryParseResult};
+use crate::document::preliminaries::{is_line_ending, is_space_or_tab};
+
+const LINK_LABEL_OPENER: char = '[';
+const LINK_LABEL_CLOSER: char = ']';
+const BACKSLASH: char = '\\';
+const MAXIMUM_LINK_LABEL_LENGTH: usize = 999;
+
+// [SPEC]: https://spec.commonmark.org/0.30/#link-label
+pub struct LinkLabel;
+
+impl LinkLabel {
+    // Labels match case-insensitively (using Unicode case folding, approximated by lowercasing
+    // and then uppercasing) and regardless of how their inner whitespace is laid out.
+    pub fn normalize(label: &str) -> String {
+        label
+            .split(|character| is_space_or_tab(character) || is_line_ending(character))
+            .filter(|word| !word.is_empty())
+            .collect::<Vec<&str>>()
+            .join(" ")
+            .to_lowercase()
+            .to_uppercase()
+    }
+}
+
+// Accepts a bracketed label, and returns its content between the brackets, with backslash
+// escapes left in place.
+impl TryParse<String> for LinkLabel {
+    fn try_parse(input: String, _document: &Document) -> TryParseResult<String> {
+        let mut characters = input.char_indices();
+
+        match characters.next() {
+            Some((_, LINK_LABEL_OPENER)) => {}
+            _ => return TryParseResult::Rejected(input),
+        }
+
+        let mut optional_closer_index = None;
+        let mut is_escaped = false;
+
+        for (index, character) in characters {
+            if is_escaped {
+                is_escaped = false;
+                continue;
+            }
+
+            match character {
+                BACKSLASH => is_escaped = true,
+                LINK_LABEL_OPENER => break,
+                LINK_LABEL_CLOSER => {
+                    optional_closer_index = Some(index);
+                    break;
+                }
+                _ => {}
+            }
+        }
+
+        match optional_closer_index {
+            Some(closer_index) => {
+                let label = &input[LINK_LABEL_OPENER.len_utf8()..closer_index];
+
+                let is_too_long = label.chars().count() > MAXIMUM_LINK_LABEL_LENGTH;
+                let is_blank = label.chars().all(char::is_whitespace);
+
+                if is_too_long || is_blank {
+                    return TryParseResult::Rejected(input);
+                }
+
+                TryParseResult::Accepted(ParseResult::new(
+                    String::from(label),
+                    String::from(&input[closer_index + LINK_LABEL_CLOSER.len_utf8()..]),
+                ))
+            }
+            None => TryParseResult::Rejected(input),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Document, LinkLabel, ParseResult, TryParse, TryParseResult};
+
+    #[test]
+    fn it_accepts_label_and_returns_leftover() {
+        let document = Document::new();
+
+        assert_eq!(
+            LinkLabel::try_parse(String::from("[foo bar]: /url"), &document),
+            TryParseResult::Accepted(ParseResult(
+                String::from("foo bar"),
+                Some(String::from(": /url"))
+            ))
+        );
+    }
+
+    #[test]
+    fn it_accepts_escaped_brackets() {
+        let document = Document::new();
+
+        assert_eq!(
+            LinkLabel::try_parse(String::from("[foo\\]\\[bar]"), &document),
+            TryParseResult::Accepted(ParseResult(String::from("foo\\]\\[bar"), None))
+        );
+    }
+
+    #[test]
+    fn it_rejects_unescaped_opening_bracket() {
+        let document = Document::new();
+
+        assert_eq!(
+            LinkLabel::try_parse(String::from("[foo[bar]"), &document),
+            TryParseResult::Rejected(String::from("[foo[bar]"))
+        );
+    }
+
+    #[test]
+    fn it_rejects_blank_label() {
+        let document = Document::new();
+
+        assert_eq!(
+            LinkLabel::try_parse(String::from("[ \n ]"), &document),
+            TryParseResult::Rejected(String::from("[ \n ]"))
+        );
+    }
+
+    #[test]
+    fn it_normalizes_case_and_whitespace() {
+        assert_eq!(
+            LinkLabel::normalize("  Foo \n\t BAR "),
+            LinkLabel::normalize("foo bar")
+        );
+        assert_eq!(LinkLabel::normalize("ẞ"), LinkLabel::normalize("SS"));
+    }
+}
@@ -0,0 +1,123 @@
+use super::{unescape, Document, Matcher, ParseResult, TryParse, TryParseResult};
+use crate::document::preliminaries::is_line_ending;
+
+const BACKSLASH: char = '\\';
+
+// [SPEC]: https://spec.commonmark.org/0.30/#link-title
+pub struct LinkTitle;
+
+impl LinkTitle {
+    // The line ending at `index` is followed by a blank line. The first half of a CRLF line
+    // ending is left for its second half to check.
+    fn is_followed_by_blank_line(text: &str, index: usize, document: &Document) -> bool {
+        let after_line_ending = &text[index + 1..];
+
+        !(text[index..].starts_with("\r\n"))
+            && document
+                .preliminaries
+                .blank_line
+                .try_match(String::from(after_line_ending))
+                .is_ok()
+    }
+}
+
+// Returns the title without its delimiters, and with backslash escapes and character references
+// decoded. A title may span multiple lines, but not contain a blank line.
+impl TryParse<String> for LinkTitle {
+    fn try_parse(input: String, document: &Document) -> TryParseResult<String> {
+        let (opener, closer) = match input.chars().next() {
+            Some('"') => ('"', '"'),
+            Some('\'') => ('\'', '\''),
+            Some('(') => ('(', ')'),
+            _ => return TryParseResult::Rejected(input),
+        };
+
+        let mut is_escaped = false;
+
+        for (index, character) in input.char_indices().skip(1) {
+            if is_escaped {
+                is_escaped = false;
+                continue;
+            }
+
+            if character == BACKSLASH {
+                is_escaped = true;
+            } else if character == closer {
+                return TryParseResult::Accepted(ParseResult::new(
+                    unescape(&input[opener.len_utf8()..index], document),
+                    String::from(&input[index + closer.len_utf8()..]),
+                ));
+            } else if character == opener
+                || (is_line_ending(character)
+                    && LinkTitle::is_followed_by_blank_line(&input, index, document))
+            {
+                break;
+            }
+        }
+
+        TryParseResult::Rejected(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Document, LinkTitle, ParseResult, TryParse, TryParseResult};
+
+    #[test]
+    fn it_accepts_all_three_delimiters() {
+        let document = Document::new();
+
+        assert_eq!(
+            LinkTitle::try_parse(String::from("\"title\" leftover"), &document),
+            TryParseResult::Accepted(ParseResult(
+                String::from("title"),
+                Some(String::from(" leftover"))
+            ))
+        );
+
+        assert_eq!(
+            LinkTitle::try_parse(String::from("'title'"), &document),
+            TryParseResult::Accepted(ParseResult(String::from("title"), None))
+        );
+
+        assert_eq!(
+            LinkTitle::try_parse(String::from("(title)"), &document),
+            TryParseResult::Accepted(ParseResult(String::from("title"), None))
+        );
+    }
+
+    #[test]
+    fn it_accepts_escaped_delimiters_and_line_endings() {
+        let document = Document::new();
+
+        assert_eq!(
+            LinkTitle::try_parse(String::from("\"ti\\\"tle\nline\""), &document),
+            TryParseResult::Accepted(ParseResult(String::from("ti\"tle\nline"), None))
+        );
+
+        assert_eq!(
+            LinkTitle::try_parse(String::from("'title\r\nline'"), &document),
+            TryParseResult::Accepted(ParseResult(String::from("title\r\nline"), None))
+        );
+    }
+
+    #[test]
+    fn it_rejects_blank_lines() {
+        let document = Document::new();
+
+        assert_eq!(
+            LinkTitle::try_parse(String::from("'title\n\nline'"), &document),
+            TryParseResult::Rejected(String::from("'title\n\nline'"))
+        );
+    }
+
+    #[test]
+    fn it_rejects_unescaped_opening_parenthesis() {
+        let document = Document::new();
+
+        assert_eq!(
+            LinkTitle::try_parse(String::from("(ti(tle)"), &document),
+            TryParseResult::Rejected(String::from("(ti(tle)"))
+        );
+    }
+}
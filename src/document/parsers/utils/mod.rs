@@ -1,3 +1,90 @@
-mod node;
+mod backslash_escape;
+mod character_reference;
+mod entities;
+mod html_tag;
+mod link_destination;
+mod link_label;
+mod link_title;
 
-pub use node::Node;
+use super::{Document, Matcher, ParseResult, TryParse, TryParseResult};
+use crate::document::preliminaries::{is_line_ending, is_space_or_tab};
+
+pub use backslash_escape::BackslashEscape;
+pub use character_reference::CharacterReference;
+pub use html_tag::HtmlTag;
+pub use link_destination::LinkDestination;
+pub use link_label::LinkLabel;
+pub use link_title::LinkTitle;
+
+// Skips optional spaces or tabs, including up to one line ending, which is the whitespace allowed
+// between the parts of links and inside of HTML tags.
+pub fn skip_whitespace(text: &str) -> &str {
+    let after_spaces_or_tabs = text.trim_start_matches(is_space_or_tab);
+    let after_line_ending = after_spaces_or_tabs
+        .strip_prefix("\r\n")
+        .or_else(|| after_spaces_or_tabs.strip_prefix(is_line_ending))
+        .unwrap_or(after_spaces_or_tabs);
+
+    after_line_ending.trim_start_matches(is_space_or_tab)
+}
+
+// Decodes backslash escapes and character references in text that isn't parsed for inlines, such
+// as link destinations, link titles and info strings.
+pub fn unescape(text: &str, document: &Document) -> String {
+    let mut unescaped = String::new();
+    let mut unconsumed = String::from(text);
+
+    while let Some(first_character) = unconsumed.chars().next() {
+        let input = match BackslashEscape::try_parse(unconsumed, document) {
+            TryParseResult::Accepted(ParseResult(escaped_character, optional_leftover)) => {
+                unescaped = [unescaped, escaped_character].concat();
+                unconsumed = optional_leftover.unwrap_or_default();
+                continue;
+            }
+            TryParseResult::Rejected(rejected_from_backslash_escape) => {
+                rejected_from_backslash_escape
+            }
+        };
+
+        let input = match CharacterReference::try_parse(input, document) {
+            TryParseResult::Accepted(ParseResult(decoded_reference, optional_leftover)) => {
+                unescaped = [unescaped, decoded_reference].concat();
+                unconsumed = optional_leftover.unwrap_or_default();
+                continue;
+            }
+            TryParseResult::Rejected(rejected_from_character_reference) => {
+                rejected_from_character_reference
+            }
+        };
+
+        unescaped.push(first_character);
+        unconsumed = String::from(&input[first_character.len_utf8()..]);
+    }
+
+    unescaped
+}
+
+// Decodes character references only, in text that is already HTML, such as attribute values of raw
+// HTML tags.
+pub fn decode_character_references(text: &str, document: &Document) -> String {
+    let mut decoded = String::new();
+    let mut unconsumed = String::from(text);
+
+    while let Some(first_character) = unconsumed.chars().next() {
+        let input = match CharacterReference::try_parse(unconsumed, document) {
+            TryParseResult::Accepted(ParseResult(decoded_reference, optional_leftover)) => {
+                decoded = [decoded, decoded_reference].concat();
+                unconsumed = optional_leftover.unwrap_or_default();
+                continue;
+            }
+            TryParseResult::Rejected(rejected_from_character_reference) => {
+                rejected_from_character_reference
+            }
+        };
+
+        decoded.push(first_character);
+        unconsumed = String::from(&input[first_character.len_utf8()..]);
+    }
+
+    decoded
+}
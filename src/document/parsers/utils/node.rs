@@ -1,4 +0,0 @@
-pub struct Node<T> {
-    pub renderer: T,
-    pub text: String,
-}
@@ -0,0 +1,149 @@
+use super::{LeftoverString, MatchedString, Matcher, RejectedString, SelectionMatcher, SPACE, TAB};
+
+// [SPEC]: https://spec.commonmark.org/0.30/#tabs
+const TAB_STOP: usize = 4;
+
+// Matches leading spaces and tabs. Since a tab advances to the next multiple of four columns, the
+// width of indentation depends on the column it starts at.
+pub struct Indentation;
+
+impl Indentation {
+    fn next_column(character: char, column: usize) -> usize {
+        match character {
+            TAB => (column / TAB_STOP + 1) * TAB_STOP,
+            _ => column + 1,
+        }
+    }
+
+    // The number of columns covered by the leading spaces and tabs of the text.
+    pub fn width(&self, text: &str, start_column: usize) -> usize {
+        text.chars()
+            .take_while(|character| *character == SPACE || *character == TAB)
+            .fold(start_column, |column, character| {
+                Indentation::next_column(character, column)
+            })
+            - start_column
+    }
+
+    // Removes up to the given number of columns of indentation. A tab that is only partially
+    // removed leaves its remaining columns as spaces. When the removal doesn't end on a tab stop,
+    // the rest of the indentation is expanded to spaces as well, since the text is then read as if
+    // it started at column zero.
+    pub fn strip(&self, text: &str, start_column: usize, columns: usize) -> String {
+        let end_column = start_column + columns;
+
+        let mut column = start_column;
+        let mut stripped = String::new();
+        let mut unconsumed = text;
+
+        while let Some(character) = unconsumed.chars().next() {
+            if character != SPACE && character != TAB {
+                break;
+            }
+
+            let next_column = Indentation::next_column(character, column);
+
+            if column >= end_column {
+                if end_column.is_multiple_of(TAB_STOP) {
+                    break;
+                }
+
+                stripped.extend(std::iter::repeat_n(SPACE, next_column - column));
+            } else if next_column > end_column {
+                stripped.extend(std::iter::repeat_n(SPACE, next_column - end_column));
+            }
+
+            column = next_column;
+            unconsumed = &unconsumed[character.len_utf8()..];
+        }
+
+        [stripped.as_str(), unconsumed].concat()
+    }
+}
+
+impl Matcher for Indentation {
+    fn try_match(&self, input: String) -> Result<(MatchedString, LeftoverString), RejectedString> {
+        let matcher = SelectionMatcher::from(vec![String::from(SPACE), String::from(TAB)]);
+
+        let mut consumed = String::new();
+        let mut unconsumed = input;
+
+        loop {
+            match matcher.try_match(unconsumed) {
+                Ok((matched_space_or_tab, leftover_from_space_or_tab)) => {
+                    consumed = [consumed, matched_space_or_tab].concat();
+                    unconsumed = leftover_from_space_or_tab;
+                }
+                Err(rejected_from_space_or_tab) => {
+                    unconsumed = rejected_from_space_or_tab;
+                    break;
+                }
+            }
+        }
+
+        Ok((consumed, unconsumed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Indentation, Matcher};
+
+    #[test]
+    fn it_accepts_leading_spaces() {
+        let indentation = Indentation;
+
+        assert_eq!(
+            indentation.try_match(String::from("    code")),
+            Ok((String::from("    "), String::from("code")))
+        );
+    }
+
+    #[test]
+    fn it_accepts_leading_tabs() {
+        let indentation = Indentation;
+
+        assert_eq!(
+            indentation.try_match(String::from(" \t\tcode")),
+            Ok((String::from(" \t\t"), String::from("code")))
+        );
+    }
+
+    #[test]
+    fn it_accepts_missing_indentation() {
+        let indentation = Indentation;
+
+        assert_eq!(
+            indentation.try_match(String::from("line")),
+            Ok((String::new(), String::from("line")))
+        );
+    }
+
+    #[test]
+    fn it_advances_tabs_to_the_next_tab_stop() {
+        let indentation = Indentation;
+
+        assert_eq!(indentation.width("\tcode", 0), 4);
+        assert_eq!(indentation.width("  \tcode", 0), 4);
+        assert_eq!(indentation.width("\t\tcode", 1), 7);
+        assert_eq!(indentation.width("code", 0), 0);
+    }
+
+    #[test]
+    fn it_strips_whole_tabs() {
+        let indentation = Indentation;
+
+        assert_eq!(indentation.strip("\t\tcode", 0, 4), "\tcode");
+        assert_eq!(indentation.strip("  \tcode\t", 0, 4), "code\t");
+        assert_eq!(indentation.strip("  code", 0, 4), "code");
+    }
+
+    #[test]
+    fn it_leaves_remaining_columns_of_partially_stripped_tabs_as_spaces() {
+        let indentation = Indentation;
+
+        assert_eq!(indentation.strip("\tcode", 0, 2), "  code");
+        assert_eq!(indentation.strip("\t\tcode", 1, 1), "      code");
+        assert_eq!(indentation.strip("\t - code", 0, 3), "  - code");
+    }
+}
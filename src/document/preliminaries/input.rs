@@ -0,0 +1,118 @@
+use super::{SourceMap, CARRIAGE_RETURN, NEWLINE};
+
+const BYTE_ORDER_MARK: &[u8] = b"\xEF\xBB\xBF";
+const NULL: char = '\u{0000}';
+const REPLACEMENT_CHARACTER: char = '\u{FFFD}';
+
+// [SPEC]: https://spec.commonmark.org/0.30/#insecure-characters
+// [SPEC]: https://spec.commonmark.org/0.30/#line-ending
+// Removes a byte order mark at the start of the input, and replaces invalid UTF-8 sequences and
+// U+0000 with the replacement character. Every line ending becomes a newline, so that the output
+// uses the same line ending throughout, whichever ones the input mixes. The source map records
+// these changes, so that positions can still refer to the original input.
+pub fn normalize_input(input: &[u8]) -> (String, SourceMap) {
+    let mut normalized = String::with_capacity(input.len());
+    let mut source_map = SourceMap::default();
+
+    let mut original_offset = 0;
+
+    if input.starts_with(BYTE_ORDER_MARK) {
+        original_offset = BYTE_ORDER_MARK.len();
+        source_map.add_shift(0, original_offset);
+    }
+
+    for chunk in input[original_offset..].utf8_chunks() {
+        let mut characters = chunk.valid().chars().peekable();
+
+        while let Some(character) = characters.next() {
+            original_offset += character.len_utf8();
+
+            match character {
+                NULL => {
+                    normalized.push(REPLACEMENT_CHARACTER);
+                    source_map.add_shift(normalized.len(), original_offset);
+                }
+                CARRIAGE_RETURN => {
+                    if characters.next_if_eq(&NEWLINE).is_some() {
+                        original_offset += NEWLINE.len_utf8();
+                    }
+
+                    normalized.push(NEWLINE);
+                    source_map.add_shift(normalized.len(), original_offset);
+                    source_map.add_line_start(original_offset);
+                }
+                NEWLINE => {
+                    normalized.push(NEWLINE);
+                    source_map.add_line_start(original_offset);
+                }
+                _ => normalized.push(character),
+            }
+        }
+
+        if !chunk.invalid().is_empty() {
+            original_offset += chunk.invalid().len();
+
+            normalized.push(REPLACEMENT_CHARACTER);
+            source_map.add_shift(normalized.len(), original_offset);
+        }
+    }
+
+    (normalized, source_map)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::normalize_input;
+    use crate::document::preliminaries::Position;
+
+    fn normalized(input: &[u8]) -> String {
+        let (normalized, _) = normalize_input(input);
+
+        normalized
+    }
+
+    #[test]
+    fn it_replaces_invalid_utf8_sequences() {
+        assert_eq!(normalized(b"caf\xc3\xa9"), "café");
+        assert_eq!(normalized(b"a\xffb\xc3"), "a\u{FFFD}b\u{FFFD}");
+    }
+
+    #[test]
+    fn it_strips_leading_byte_order_mark() {
+        assert_eq!(normalized("\u{FEFF}# foo".as_bytes()), "# foo");
+        assert_eq!(normalized("foo\u{FEFF}".as_bytes()), "foo\u{FEFF}");
+    }
+
+    #[test]
+    fn it_replaces_null_characters() {
+        assert_eq!(normalized(b"a\x00b"), "a\u{FFFD}b");
+    }
+
+    #[test]
+    fn it_normalizes_line_endings() {
+        assert_eq!(normalized(b"a\r\nb\rc\n\r\r\nd"), "a\nb\nc\n\n\nd");
+    }
+
+    #[test]
+    fn it_maps_normalized_offsets_to_original_positions() {
+        let (normalized, source_map) = normalize_input(b"\xEF\xBB\xBFa\x00\r\n\xffb\rc");
+
+        assert_eq!(normalized, "a\u{FFFD}\n\u{FFFD}b\nc");
+        assert_eq!(
+            source_map.position(normalized.find('b').unwrap_or_default()),
+            Position {
+                offset: 8,
+                line: 2,
+                column: 2
+            }
+        );
+        assert_eq!(
+            source_map.position(normalized.find('c').unwrap_or_default()),
+            Position {
+                offset: 10,
+                line: 3,
+                column: 1
+            }
+        );
+    }
+}
@@ -1,34 +1,66 @@
 mod blank_line;
 mod character;
+mod indentation;
+mod input;
 mod line;
 mod line_ending;
+mod source_map;
 mod space;
-mod whitespace;
 
 use super::{
     IndividualMatcher, LeftoverString, MatchedString, Matcher, RejectedString, SelectionMatcher,
 };
 use blank_line::BlankLine;
 use character::Character;
+use indentation::Indentation;
 use line::Line;
 use line_ending::LineEnding;
 use space::Space;
-use whitespace::Whitespace;
 
-const CARRIAGE_RETURN: char = '\u{000D}';
-const FORM_FEED: char = '\u{000C}';
-const LINE_TABULATION: char = '\u{000B}';
-const NEWLINE: char = '\u{000A}';
-const SPACE: char = '\u{0020}';
-const TAB: char = '\u{0009}';
+pub use input::normalize_input;
+pub use source_map::{Position, SourceMap, Span};
+
+pub const CARRIAGE_RETURN: char = '\u{000D}';
+pub const FORM_FEED: char = '\u{000C}';
+pub const NEWLINE: char = '\u{000A}';
+pub const SPACE: char = '\u{0020}';
+pub const TAB: char = '\u{0009}';
+
+pub fn is_space_or_tab(character: char) -> bool {
+    character == SPACE || character == TAB
+}
+
+pub fn is_line_ending(character: char) -> bool {
+    character == NEWLINE || character == CARRIAGE_RETURN
+}
+
+// [SPEC]: https://spec.commonmark.org/0.30/#unicode-whitespace-character
+pub fn is_unicode_whitespace(character: char) -> bool {
+    matches!(
+        character,
+        TAB | NEWLINE | FORM_FEED | CARRIAGE_RETURN | SPACE | '\u{00A0}' | '\u{1680}' | '\u{2000}'
+            ..='\u{200A}' | '\u{202F}' | '\u{205F}' | '\u{3000}'
+    )
+}
+
+// [SPEC]: https://spec.commonmark.org/0.30/#unicode-punctuation-character
+// Outside of ASCII, anything that isn't alphanumeric, whitespace or a control character is treated
+// as punctuation, which approximates the Unicode punctuation and symbol categories.
+pub fn is_punctuation(character: char) -> bool {
+    character.is_ascii_punctuation()
+        || (!character.is_ascii()
+            && !character.is_alphanumeric()
+            && !character.is_whitespace()
+            && !character.is_control())
+}
 
 pub struct Preliminaries {
     pub blank_line: BlankLine,
     pub character: Character,
+    pub indentation: Indentation,
     pub line: Line,
     pub line_ending: LineEnding,
     pub space: Space,
-    pub whitespace: Whitespace,
 }
 
 impl Preliminaries {
@@ -36,10 +68,10 @@ impl Preliminaries {
         Preliminaries {
             blank_line: BlankLine,
             character: Character,
+            indentation: Indentation,
             line: Line,
             line_ending: LineEnding,
             space: Space,
-            whitespace: Whitespace,
         }
     }
 }
@@ -0,0 +1,151 @@
+// A position in the original input. Lines and columns start at 1, and columns count bytes from
+// the start of the line, like offsets count bytes from the start of the input.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Position {
+    pub offset: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+// The part of the original input that a node was parsed from, which ends right before `end`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Span {
+    pub start: Position,
+    pub end: Position,
+}
+
+impl Span {
+    // Spans of inlines are first relative to the text they're parsed from, and only hold offsets,
+    // until they're mapped to the input.
+    pub(crate) fn from_offsets(start: usize, end: usize) -> Span {
+        Span {
+            start: Position {
+                offset: start,
+                ..Position::default()
+            },
+            end: Position {
+                offset: end,
+                ..Position::default()
+            },
+        }
+    }
+}
+
+// Maps offsets in the normalized input, which is what gets parsed, to positions in the original
+// input.
+#[derive(Default)]
+pub struct SourceMap {
+    // Pairs of offsets in the normalized and original input, from which on the difference between
+    // them changes, because a character before was removed or replaced.
+    shifts: Vec<(usize, usize)>,
+    // Offsets in the original input where each line after the first starts.
+    line_starts: Vec<usize>,
+}
+
+impl SourceMap {
+    pub fn add_shift(&mut self, offset: usize, original_offset: usize) {
+        self.shifts.push((offset, original_offset));
+    }
+
+    pub fn add_line_start(&mut self, original_offset: usize) {
+        self.line_starts.push(original_offset);
+    }
+
+    pub fn position(&self, offset: usize) -> Position {
+        let original_offset = match self.shifts.partition_point(|(from, _)| *from <= offset) {
+            0 => offset,
+            index => {
+                let (from, original_from) = self.shifts[index - 1];
+
+                original_from + offset - from
+            }
+        };
+
+        let line_index = self
+            .line_starts
+            .partition_point(|line_start| *line_start <= original_offset);
+        let line_start = match line_index {
+            0 => 0,
+            _ => self.line_starts[line_index - 1],
+        };
+
+        Position {
+            offset: original_offset,
+            line: line_index + 1,
+            column: original_offset - line_start + 1,
+        }
+    }
+
+    pub fn span(&self, start: usize, end: usize) -> Span {
+        Span {
+            start: self.position(start),
+            end: self.position(end),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Position, SourceMap};
+
+    #[test]
+    fn it_maps_offsets_to_lines_and_columns() {
+        let mut source_map = SourceMap::default();
+
+        source_map.add_line_start(4);
+        source_map.add_line_start(5);
+
+        assert_eq!(
+            source_map.position(2),
+            Position {
+                offset: 2,
+                line: 1,
+                column: 3
+            }
+        );
+        assert_eq!(
+            source_map.position(4),
+            Position {
+                offset: 4,
+                line: 2,
+                column: 1
+            }
+        );
+        assert_eq!(
+            source_map.position(7),
+            Position {
+                offset: 7,
+                line: 3,
+                column: 3
+            }
+        );
+    }
+
+    #[test]
+    fn it_maps_offsets_past_changed_characters_to_original_offsets() {
+        let mut source_map = SourceMap::default();
+
+        // A byte order mark was removed at the start, and a carriage return before a newline at
+        // offset 5 of the original input.
+        source_map.add_shift(0, 3);
+        source_map.add_shift(3, 7);
+        source_map.add_line_start(7);
+
+        assert_eq!(
+            source_map.position(1),
+            Position {
+                offset: 4,
+                line: 1,
+                column: 5
+            }
+        );
+        assert_eq!(
+            source_map.position(4),
+            Position {
+                offset: 8,
+                line: 2,
+                column: 2
+            }
+        );
+    }
+}
@@ -0,0 +1,90 @@
+use super::{escape_html, Render};
+
+// [SPEC]: https://html.spec.whatwg.org/multipage/syntax.html#attributes-2
+// Attributes are rendered in the order they were first added. Adding an attribute with a key that
+// is already present, compared case-insensitively like HTML does, replaces its value in place.
+#[derive(Default)]
+pub struct Attributes(Vec<(String, Option<String>)>);
+
+impl Attributes {
+    pub fn insert(&mut self, key: String, value: String) {
+        self.insert_optional(key, Some(value));
+    }
+
+    // [SPEC]: https://html.spec.whatwg.org/multipage/common-microsyntaxes.html#boolean-attributes
+    pub fn insert_boolean(&mut self, key: String) {
+        self.insert_optional(key, None);
+    }
+
+    fn insert_optional(&mut self, key: String, optional_value: Option<String>) {
+        let Attributes(attributes) = self;
+
+        match attributes
+            .iter_mut()
+            .find(|(existing_key, _)| existing_key.eq_ignore_ascii_case(&key))
+        {
+            Some((_, existing_value)) => *existing_value = optional_value,
+            None => attributes.push((key, optional_value)),
+        }
+    }
+}
+
+// Every attribute is preceded by a space, so the result can follow the tag name directly.
+impl Render for Attributes {
+    fn render(&self) -> String {
+        let Attributes(attributes) = self;
+
+        attributes
+            .iter()
+            .map(|(key, optional_value)| match optional_value {
+                Some(value) => format!(" {}=\"{}\"", key, escape_html(value)),
+                None => format!(" {}", key),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Attributes, Render};
+
+    #[test]
+    fn it_renders_attributes_in_insertion_order_with_escaped_values() {
+        let mut attributes = Attributes::default();
+
+        attributes.insert(String::from("src"), String::from("/a?b=1&c=2"));
+        attributes.insert(String::from("alt"), String::from("\"quoted\""));
+
+        assert_eq!(
+            attributes.render(),
+            " src=\"/a?b=1&amp;c=2\" alt=\"&quot;quoted&quot;\""
+        );
+    }
+
+    #[test]
+    fn it_renders_boolean_attributes_without_value() {
+        let mut attributes = Attributes::default();
+
+        attributes.insert(String::from("type"), String::from("checkbox"));
+        attributes.insert_boolean(String::from("checked"));
+        attributes.insert_boolean(String::from("disabled"));
+
+        assert_eq!(attributes.render(), " type=\"checkbox\" checked disabled");
+    }
+
+    #[test]
+    fn it_replaces_value_of_duplicate_key_in_place() {
+        let mut attributes = Attributes::default();
+
+        attributes.insert(String::from("class"), String::from("first"));
+        attributes.insert(String::from("id"), String::from("foo"));
+        attributes.insert(String::from("CLASS"), String::from("second"));
+
+        assert_eq!(attributes.render(), " class=\"second\" id=\"foo\"");
+    }
+
+    #[test]
+    fn it_renders_nothing_without_attributes() {
+        assert_eq!(Attributes::default().render(), "");
+    }
+}
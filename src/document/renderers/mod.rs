@@ -1,7 +1,52 @@
+pub mod attributes;
 pub mod normal_element;
+pub mod raw_element;
 pub mod text_element;
+pub mod url;
 pub mod void_element;
 
 pub trait Render {
     fn render(&self) -> String;
 }
+
+// How block elements are laid out in the output. `Spec` matches the CommonMark reference output
+// byte for byte, with a line ending after each block element and after the start tag of each
+// container. `Compact` leaves out those line endings, and `Pretty` adds indentation on top of
+// them, for debugging. The content of elements is never changed.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OutputFormat {
+    Compact,
+    Pretty,
+    Spec,
+}
+
+// [SPEC]: https://html.spec.whatwg.org/multipage/parsing.html#serialising-html-fragments
+// Escapes the characters that could otherwise be read as markup, in text and attribute values.
+pub fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for character in text.chars() {
+        match character {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            character => escaped.push(character),
+        }
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::escape_html;
+
+    #[test]
+    fn it_escapes_html_special_characters() {
+        assert_eq!(
+            escape_html("a < b && \"c\" > 'd'"),
+            "a &lt; b &amp;&amp; &quot;c&quot; &gt; 'd'"
+        );
+    }
+}
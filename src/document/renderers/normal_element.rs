@@ -1,8 +1,8 @@
-use super::Render;
+use super::{attributes::Attributes, Render};
 
 // [SPEC]: https://html.spec.whatwg.org/multipage/syntax.html#elements-2
 pub struct NormalElement {
-    attributes: Vec<(String, String)>,
+    attributes: Attributes,
     inner_text: String,
     tag: String,
 }
@@ -10,22 +10,23 @@ pub struct NormalElement {
 impl NormalElement {
     pub fn new(tag: String, inner_text: String) -> NormalElement {
         NormalElement {
-            attributes: Vec::new(),
+            attributes: Attributes::default(),
             inner_text,
             tag,
         }
     }
 
     pub fn add_attribute(&mut self, key: String, value: String) {
-        self.attributes.push((key, value));
+        self.attributes.insert(key, value);
     }
 }
 
 impl Render for NormalElement {
     fn render(&self) -> String {
         format!(
-            "<{opening_tag}>{inner_text}</{closing_tag}>",
+            "<{opening_tag}{attributes}>{inner_text}</{closing_tag}>",
             opening_tag = self.tag,
+            attributes = self.attributes.render(),
             inner_text = self.inner_text,
             closing_tag = self.tag
         )
@@ -0,0 +1,12 @@
+use super::Render;
+
+// Markup that is already valid HTML, such as raw HTML from the input, is rendered as is.
+pub struct RawElement(pub String);
+
+impl Render for RawElement {
+    fn render(&self) -> String {
+        let RawElement(html) = self;
+
+        String::from(html)
+    }
+}
@@ -1,4 +1,4 @@
-use super::Render;
+use super::{escape_html, Render};
 
 // [SPEC]: https://html.spec.whatwg.org/multipage/syntax.html#elements-2
 pub struct TextElement(pub String);
@@ -7,6 +7,6 @@ impl Render for TextElement {
     fn render(&self) -> String {
         let TextElement(text) = self;
 
-        String::from(text)
+        escape_html(text)
     }
 }
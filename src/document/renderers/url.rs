@@ -0,0 +1,66 @@
+// Characters that are kept as they are in URLs, as in the cmark reference implementation. Any other
+// character, including non-ASCII ones, is percent-encoded as UTF-8.
+const URL_SAFE_CHARACTERS: &str = "-_.+!*'(),%#@?=;:/&$~";
+const PERCENT_SIGN: char = '%';
+
+// Percent-encodes a link or image destination, so that it can be used as a valid URL. Existing
+// `%XX` escapes are kept, while a percent sign that doesn't start one is encoded itself.
+pub fn normalize_url(url: &str) -> String {
+    let mut normalized = String::with_capacity(url.len());
+
+    for (index, character) in url.char_indices() {
+        let is_escape = character == PERCENT_SIGN
+            && url[index + PERCENT_SIGN.len_utf8()..]
+                .chars()
+                .take(2)
+                .filter(char::is_ascii_hexdigit)
+                .count()
+                == 2;
+
+        if is_escape
+            || (character != PERCENT_SIGN
+                && (character.is_ascii_alphanumeric() || URL_SAFE_CHARACTERS.contains(character)))
+        {
+            normalized.push(character);
+            continue;
+        }
+
+        let mut bytes = [0; 4];
+
+        for byte in character.encode_utf8(&mut bytes).bytes() {
+            normalized.push_str(&format!("%{:02X}", byte));
+        }
+    }
+
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::normalize_url;
+
+    #[test]
+    fn it_keeps_safe_characters() {
+        assert_eq!(
+            normalize_url("https://example.com/a-b_c.d?e=f&g=(h)#i~j"),
+            "https://example.com/a-b_c.d?e=f&g=(h)#i~j"
+        );
+    }
+
+    #[test]
+    fn it_percent_encodes_unsafe_and_non_ascii_characters() {
+        assert_eq!(normalize_url("/my file.md"), "/my%20file.md");
+        assert_eq!(normalize_url("/über"), "/%C3%BCber");
+        assert_eq!(
+            normalize_url("a[b]\\c`d\"e<f>"),
+            "a%5Bb%5D%5Cc%60d%22e%3Cf%3E"
+        );
+    }
+
+    #[test]
+    fn it_keeps_valid_percent_escapes_only() {
+        assert_eq!(normalize_url("foo%20b%c3%a4"), "foo%20b%c3%a4");
+        assert_eq!(normalize_url("100%"), "100%25");
+        assert_eq!(normalize_url("%zz%2"), "%25zz%252");
+    }
+}
@@ -1,26 +1,77 @@
-use super::Render;
+use super::{attributes::Attributes, Render};
+
+// Whether void elements are closed XHTML-style, as in `<hr />`, which is what the CommonMark
+// reference output uses, or left open HTML5-style, as in `<hr>`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum VoidElementStyle {
+    Html5,
+    Xhtml,
+}
 
 // [SPEC]: https://html.spec.whatwg.org/multipage/syntax.html#elements-2
 pub struct VoidElement {
-    attributes: Vec<(String, String)>,
+    attributes: Attributes,
+    style: VoidElementStyle,
     tag: String,
 }
 
 impl VoidElement {
-    pub fn new(tag: String) -> VoidElement {
+    pub fn new(tag: String, style: VoidElementStyle) -> VoidElement {
+        VoidElement::with_attributes(tag, Attributes::default(), style)
+    }
+
+    pub fn with_attributes(
+        tag: String,
+        attributes: Attributes,
+        style: VoidElementStyle,
+    ) -> VoidElement {
         VoidElement {
-            attributes: Vec::new(),
+            attributes,
+            style,
             tag,
         }
     }
 
     pub fn add_attribute(&mut self, key: String, value: String) {
-        self.attributes.push((key, value));
+        self.attributes.insert(key, value);
     }
 }
 
 impl Render for VoidElement {
     fn render(&self) -> String {
-        format!("<{tag} />", tag = self.tag)
+        let closer = match self.style {
+            VoidElementStyle::Html5 => ">",
+            VoidElementStyle::Xhtml => " />",
+        };
+
+        format!(
+            "<{tag}{attributes}{closer}",
+            tag = self.tag,
+            attributes = self.attributes.render(),
+            closer = closer
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Render, VoidElement, VoidElementStyle};
+
+    #[test]
+    fn it_renders_in_xhtml_style() {
+        let mut image_element = VoidElement::new(String::from("img"), VoidElementStyle::Xhtml);
+
+        image_element.add_attribute(String::from("src"), String::from("a.png"));
+
+        assert_eq!(image_element.render(), "<img src=\"a.png\" />");
+    }
+
+    #[test]
+    fn it_renders_in_html5_style() {
+        let mut image_element = VoidElement::new(String::from("img"), VoidElementStyle::Html5);
+
+        image_element.add_attribute(String::from("src"), String::from("a.png"));
+
+        assert_eq!(image_element.render(), "<img src=\"a.png\">");
     }
 }
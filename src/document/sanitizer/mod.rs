@@ -0,0 +1,359 @@
+mod tag_balancer;
+
+use super::{
+    parsers::{
+        utils::{decode_character_references, HtmlTag},
+        ParseResult, TryParse, TryParseResult,
+    },
+    renderers::{attributes::Attributes, escape_html, void_element::VoidElement, Render},
+    Document,
+};
+use std::collections::HashMap;
+
+pub use tag_balancer::TagBalancer;
+
+const TAG_OPENER: char = '<';
+const CLOSING_TAG_OPENER: &str = "</";
+const SCHEME_SEPARATOR: char = ':';
+
+// [SPEC]: https://html.spec.whatwg.org/multipage/syntax.html#void-elements
+const VOID_ELEMENTS: [&str; 13] = [
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+// Attributes whose value is a URL, which is only kept when its scheme is allowed.
+const URL_ATTRIBUTES: [&str; 8] = [
+    "action",
+    "background",
+    "cite",
+    "formaction",
+    "href",
+    "longdesc",
+    "poster",
+    "src",
+];
+
+const DEFAULT_ALLOWED_TAGS: [(&str, &[&str]); 42] = [
+    ("a", &["href", "title"]),
+    ("abbr", &["title"]),
+    ("b", &[]),
+    ("blockquote", &["cite"]),
+    ("br", &[]),
+    ("code", &[]),
+    ("dd", &[]),
+    ("del", &[]),
+    ("details", &["open"]),
+    ("div", &[]),
+    ("dl", &[]),
+    ("dt", &[]),
+    ("em", &[]),
+    ("h1", &[]),
+    ("h2", &[]),
+    ("h3", &[]),
+    ("h4", &[]),
+    ("h5", &[]),
+    ("h6", &[]),
+    ("hr", &[]),
+    ("i", &[]),
+    ("img", &["src", "alt", "title", "width", "height"]),
+    ("ins", &[]),
+    ("kbd", &[]),
+    ("li", &[]),
+    ("ol", &["start"]),
+    ("p", &[]),
+    ("pre", &[]),
+    ("q", &["cite"]),
+    ("s", &[]),
+    ("samp", &[]),
+    ("span", &[]),
+    ("strong", &[]),
+    ("sub", &[]),
+    ("summary", &[]),
+    ("sup", &[]),
+    ("table", &[]),
+    ("tbody", &[]),
+    ("td", &[]),
+    ("th", &[]),
+    ("thead", &[]),
+    ("tr", &[]),
+];
+
+const DEFAULT_ALLOWED_URL_SCHEMES: [&str; 3] = ["http", "https", "mailto"];
+
+// Decides which parts of raw HTML are kept when it is sanitized. Only allowlisted tags are kept,
+// with only their allowlisted attributes. URL attributes are dropped when their scheme isn't
+// allowed, while relative URLs are always allowed.
+pub struct SanitizerPolicy {
+    allowed_tags: HashMap<String, Vec<String>>,
+    allowed_url_schemes: Vec<String>,
+}
+
+impl SanitizerPolicy {
+    // The default policy allows common formatting tags, such as `<kbd>`, `<sup>` and `<details>`,
+    // and links and images over HTTP(S).
+    pub fn new() -> SanitizerPolicy {
+        let mut policy = SanitizerPolicy::empty();
+
+        for (tag, attributes) in DEFAULT_ALLOWED_TAGS {
+            policy.allow_tag(tag, attributes);
+        }
+
+        for scheme in DEFAULT_ALLOWED_URL_SCHEMES {
+            policy.allow_url_scheme(scheme);
+        }
+
+        policy
+    }
+
+    // A policy that allows nothing, to add allowed tags and URL schemes to.
+    pub fn empty() -> SanitizerPolicy {
+        SanitizerPolicy {
+            allowed_tags: HashMap::new(),
+            allowed_url_schemes: Vec::new(),
+        }
+    }
+
+    // Allowing a tag again replaces its allowed attributes. Names are case-insensitive.
+    pub fn allow_tag(&mut self, tag: &str, attributes: &[&str]) {
+        self.allowed_tags.insert(
+            tag.to_ascii_lowercase(),
+            attributes
+                .iter()
+                .map(|attribute| attribute.to_ascii_lowercase())
+                .collect(),
+        );
+    }
+
+    pub fn allow_url_scheme(&mut self, scheme: &str) {
+        self.allowed_url_schemes.push(scheme.to_ascii_lowercase());
+    }
+
+    // Keeps allowed tags, and escapes everything else that could be read as markup. Tags that the
+    // HTML opens are tracked by the tag balancer, which closes them if the HTML doesn't.
+    pub fn sanitize(
+        &self,
+        html: &str,
+        document: &Document,
+        tag_balancer: &mut TagBalancer,
+    ) -> String {
+        let mut sanitized = String::new();
+        let mut unconsumed = String::from(html);
+
+        while !unconsumed.is_empty() {
+            let input = match HtmlTag::try_parse(unconsumed, document) {
+                TryParseResult::Accepted(ParseResult(matched_tag, optional_leftover)) => {
+                    sanitized = [
+                        sanitized,
+                        self.sanitize_tag(&matched_tag, document, tag_balancer),
+                    ]
+                    .concat();
+                    unconsumed = optional_leftover.unwrap_or_default();
+                    continue;
+                }
+                TryParseResult::Rejected(rejected_from_html_tag) => rejected_from_html_tag,
+            };
+
+            // Anything up to the next possible tag is text, where only `<` and `>` need to be
+            // escaped, since character references are already valid HTML.
+            // The text takes at least its first character, which may not be the tag opener.
+            let first_length = input.chars().next().map_or(0, char::len_utf8);
+            let text_length = input[first_length..]
+                .find(TAG_OPENER)
+                .map_or(input.len(), |index| index + first_length);
+            let text = input[..text_length]
+                .replace('<', "&lt;")
+                .replace('>', "&gt;");
+
+            sanitized = [sanitized, text].concat();
+            unconsumed = String::from(&input[text_length..]);
+        }
+
+        sanitized
+    }
+
+    fn sanitize_tag(
+        &self,
+        tag: &str,
+        document: &Document,
+        tag_balancer: &mut TagBalancer,
+    ) -> String {
+        let name = HtmlTag::name(tag);
+
+        let allowed_attributes = match self.allowed_tags.get(&name) {
+            Some(allowed_attributes) => allowed_attributes,
+            None => return escape_html(tag),
+        };
+
+        // Closing tags that don't close an open tag are dropped, so that the output stays
+        // balanced.
+        if tag.starts_with(CLOSING_TAG_OPENER) {
+            return tag_balancer.close(&name).unwrap_or_default();
+        }
+
+        let mut attributes = Attributes::default();
+
+        for (key, optional_value) in HtmlTag::attributes(tag) {
+            let key = key.to_ascii_lowercase();
+
+            if !allowed_attributes.contains(&key) {
+                continue;
+            }
+
+            match optional_value {
+                Some(value) => {
+                    let value = decode_character_references(&value, document);
+
+                    if URL_ATTRIBUTES.contains(&key.as_str()) && !self.is_allowed_url(&value) {
+                        continue;
+                    }
+
+                    attributes.insert(key, value);
+                }
+                None => attributes.insert_boolean(key),
+            }
+        }
+
+        if VOID_ELEMENTS.contains(&name.as_str()) {
+            return VoidElement::with_attributes(name, attributes, document.void_element_style)
+                .render();
+        }
+
+        let start_tag = format!("<{}{}>", name, attributes.render());
+
+        tag_balancer.open(name);
+
+        start_tag
+    }
+
+    // Browsers ignore whitespace and control characters in URL schemes, so they're ignored here as
+    // well. A colon after a slash, question mark or number sign isn't part of a scheme.
+    fn is_allowed_url(&self, url: &str) -> bool {
+        let url: String = url
+            .chars()
+            .filter(|character| !character.is_ascii_whitespace() && !character.is_control())
+            .collect();
+
+        match url.find(['/', '?', '#', SCHEME_SEPARATOR]) {
+            Some(index) if url[index..].starts_with(SCHEME_SEPARATOR) => self
+                .allowed_url_schemes
+                .contains(&url[..index].to_ascii_lowercase()),
+            _ => true,
+        }
+    }
+}
+
+impl Default for SanitizerPolicy {
+    fn default() -> SanitizerPolicy {
+        SanitizerPolicy::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Document, SanitizerPolicy, TagBalancer};
+
+    fn sanitize(policy: &SanitizerPolicy, html: &str) -> String {
+        let document = Document::new();
+        let mut tag_balancer = TagBalancer::default();
+
+        tag_balancer.enter();
+
+        [
+            policy.sanitize(html, &document, &mut tag_balancer),
+            tag_balancer.leave(),
+        ]
+        .concat()
+    }
+
+    #[test]
+    fn it_keeps_allowed_tags_and_attributes() {
+        let policy = SanitizerPolicy::new();
+
+        assert_eq!(
+            sanitize(
+                &policy,
+                "<details open class=\"x\"><summary>Hi</summary>Press <KBD>Ctrl</KBD></details>"
+            ),
+            "<details open><summary>Hi</summary>Press <kbd>Ctrl</kbd></details>"
+        );
+        assert_eq!(
+            sanitize(
+                &policy,
+                "<img src=\"a.png\" alt='A &amp; B' onerror=\"x()\" width=10 />"
+            ),
+            "<img src=\"a.png\" alt=\"A &amp; B\" width=\"10\" />"
+        );
+    }
+
+    #[test]
+    fn it_escapes_everything_else() {
+        let policy = SanitizerPolicy::new();
+
+        assert_eq!(
+            sanitize(&policy, "<script>alert(1)</script><!-- x --> 1 < 2"),
+            "&lt;script&gt;alert(1)&lt;/script&gt;&lt;!-- x --&gt; 1 &lt; 2"
+        );
+    }
+
+    #[test]
+    fn it_keeps_non_ascii_text_after_tags() {
+        let policy = SanitizerPolicy::new();
+
+        assert_eq!(sanitize(&policy, "<div>é</div>"), "<div>é</div>");
+        assert_eq!(
+            sanitize(&policy, "<script>日本<b>語</b>"),
+            "&lt;script&gt;日本<b>語</b>"
+        );
+    }
+
+    #[test]
+    fn it_balances_unclosed_tags_and_drops_stray_closing_tags() {
+        let policy = SanitizerPolicy::new();
+
+        assert_eq!(
+            sanitize(&policy, "</sup><sup><kbd>x</sup>"),
+            "<sup><kbd>x</kbd></sup>"
+        );
+        assert_eq!(
+            sanitize(&policy, "<div><span>x"),
+            "<div><span>x</span></div>"
+        );
+    }
+
+    #[test]
+    fn it_drops_url_attributes_with_disallowed_schemes() {
+        let policy = SanitizerPolicy::new();
+
+        assert_eq!(
+            sanitize(
+                &policy,
+                "<a href=\"java&#x09;script:alert(1)\">a</a><a href=\"&#106;avascript:x\">b</a>"
+            ),
+            "<a>a</a><a>b</a>"
+        );
+        assert_eq!(
+            sanitize(
+                &policy,
+                "<a href=\"/x:y\">a</a><a href=\"HTTPS://example.com\">b</a>"
+            ),
+            "<a href=\"/x:y\">a</a><a href=\"HTTPS://example.com\">b</a>"
+        );
+    }
+
+    #[test]
+    fn it_follows_configured_policy() {
+        let mut policy = SanitizerPolicy::empty();
+
+        policy.allow_tag("A", &["HREF"]);
+        policy.allow_url_scheme("ftp");
+
+        assert_eq!(
+            sanitize(
+                &policy,
+                "<a href=\"ftp://x\"><b>y</b></a><a href=\"https://x\">"
+            ),
+            "<a href=\"ftp://x\">&lt;b&gt;y&lt;/b&gt;</a><a></a>"
+        );
+    }
+}
@@ -0,0 +1,88 @@
+// Keeps track of the tags that sanitized raw HTML has opened, so that the ones it doesn't close
+// can be closed for it. Tags are scoped: those opened after `enter` can only be closed before the
+// matching `leave`, which closes any that are still open.
+#[derive(Default)]
+pub struct TagBalancer {
+    floors: Vec<usize>,
+    open_tags: Vec<String>,
+}
+
+impl TagBalancer {
+    pub fn enter(&mut self) {
+        self.floors.push(self.open_tags.len());
+    }
+
+    pub fn leave(&mut self) -> String {
+        let floor = self.floors.pop().unwrap_or_default();
+
+        self.close_down_to(floor)
+    }
+
+    pub fn open(&mut self, tag: String) {
+        self.open_tags.push(tag);
+    }
+
+    // Closes the most recently opened tag with the name, along with the tags opened after it. A
+    // tag that isn't open in the current scope can't be closed.
+    pub fn close(&mut self, tag: &str) -> Option<String> {
+        let floor = self.floors.last().copied().unwrap_or_default();
+        let index = self.open_tags[floor..]
+            .iter()
+            .rposition(|open_tag| open_tag == tag)?;
+
+        Some(self.close_down_to(floor + index))
+    }
+
+    fn close_down_to(&mut self, length: usize) -> String {
+        self.open_tags
+            .drain(length..)
+            .rev()
+            .map(|tag| format!("</{}>", tag))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TagBalancer;
+
+    #[test]
+    fn it_closes_tags_left_open_when_leaving() {
+        let mut tag_balancer = TagBalancer::default();
+
+        tag_balancer.enter();
+        tag_balancer.open(String::from("details"));
+        tag_balancer.open(String::from("kbd"));
+
+        assert_eq!(tag_balancer.leave(), "</kbd></details>");
+    }
+
+    #[test]
+    fn it_closes_tags_opened_after_the_closed_one() {
+        let mut tag_balancer = TagBalancer::default();
+
+        tag_balancer.open(String::from("sup"));
+        tag_balancer.open(String::from("kbd"));
+
+        assert_eq!(
+            tag_balancer.close("sup"),
+            Some(String::from("</kbd></sup>"))
+        );
+        assert_eq!(tag_balancer.close("sup"), None);
+    }
+
+    #[test]
+    fn it_does_not_close_tags_opened_outside_of_scope() {
+        let mut tag_balancer = TagBalancer::default();
+
+        tag_balancer.open(String::from("details"));
+        tag_balancer.enter();
+
+        assert_eq!(tag_balancer.close("details"), None);
+        assert_eq!(tag_balancer.leave(), "");
+        assert_eq!(
+            tag_balancer.close("details"),
+            Some(String::from("</details>"))
+        );
+    }
+}
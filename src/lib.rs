@@ -2,7 +2,13 @@ pub mod document;
 
 use document::Document;
 
+// Renders the input to HTML. `Document::parse` returns the syntax tree instead.
 pub fn parse(input: String) -> String {
-    let document = Document::new();
+    let mut document = Document::new();
     document.render(input)
 }
+
+pub fn parse_bytes(input: &[u8]) -> String {
+    let mut document = Document::new();
+    document.render_bytes(input)
+}
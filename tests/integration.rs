@@ -1,7 +1,7 @@
 #[test]
 fn it_parses_standard_example() {
     let input = String::from("***\nLorem ipsum");
-    let expected = String::from("<hr /><p>Lorem ipsum</p>");
+    let expected = String::from("<hr />\n<p>Lorem ipsum</p>\n");
 
     assert_eq!(rustdown::parse(input), expected);
 }
@@ -12,11 +12,328 @@ fn it_parses_variant_example() {}
 #[test]
 fn it_prioritizes_code_block_over_inline_code_span() {
     let code_block_input = String::from("```Code block, not span```");
-    let expected_code_block = String::from("<pre><code>Code block, not span</code></pre>");
+    let expected_code_block = String::from("<pre><code>Code block, not span</code></pre>\n");
 
     let code_span_input = String::from("``Code span, not block``");
-    let expected_code_span = String::from("<p><code>Code span, not block</code></p>");
+    let expected_code_span = String::from("<p><code>Code span, not block</code></p>\n");
 
     assert_eq!(rustdown::parse(code_block_input), expected_code_block);
     assert_eq!(rustdown::parse(code_span_input), expected_code_span);
 }
+
+#[test]
+fn it_parses_atx_headings() {
+    let input = String::from("# Title\n\n## Section ##\nLorem ipsum\n#hashtag");
+    let expected = String::from("<h1>Title</h1>\n<h2>Section</h2>\n<p>Lorem ipsum\n#hashtag</p>\n");
+
+    assert_eq!(rustdown::parse(input), expected);
+}
+
+#[test]
+fn it_parses_setext_headings() {
+    let input = String::from("Title\n=====\n\nSection\n---\n\n---");
+    let expected = String::from("<h1>Title</h1>\n<h2>Section</h2>\n<hr />\n");
+
+    assert_eq!(rustdown::parse(input), expected);
+}
+
+#[test]
+fn it_parses_fenced_code_blocks() {
+    let input = String::from("Lorem ipsum\n~~~ rust ignore\nfn main() {}\n~~~\n\n```\nunclosed");
+    let expected = String::from(
+        "<p>Lorem ipsum</p>\n<pre><code class=\"language-rust\">fn main() {}\n</code></pre>\n<pre><code>unclosed\n</code></pre>\n",
+    );
+
+    assert_eq!(rustdown::parse(input), expected);
+}
+
+#[test]
+fn it_parses_indented_code_blocks() {
+    let input = String::from("    $ cargo test\n\n      running 1 test\nLorem\n    ipsum");
+    let expected = String::from(
+        "<pre><code>$ cargo test\n\n  running 1 test\n</code></pre>\n<p>Lorem\nipsum</p>\n",
+    );
+
+    assert_eq!(rustdown::parse(input), expected);
+}
+
+#[test]
+fn it_parses_lists() {
+    let input = String::from("- foo\n- * * *\n\n3) bar\n\n   baz\n4) qux");
+    let expected = String::from(
+        "<ul>\n<li>foo</li>\n<li>\n<hr />\n</li>\n</ul>\n<ol start=\"3\">\n<li>\n<p>bar</p>\n<p>baz</p>\n</li>\n<li>\n<p>qux</p>\n</li>\n</ol>\n",
+    );
+
+    assert_eq!(rustdown::parse(input), expected);
+}
+
+#[test]
+fn it_omits_link_reference_definitions() {
+    let input = String::from(
+        "[foo]: /url \"title\"\n[bar]:\n  <my url>\nLorem [ipsum]: /x\n\n> [baz]: /baz",
+    );
+    let expected = String::from("<p>Lorem [ipsum]: /x</p>\n<blockquote>\n</blockquote>\n");
+
+    assert_eq!(rustdown::parse(input), expected);
+}
+
+#[test]
+fn it_parses_html_blocks() {
+    let input =
+        String::from("<details>\n<summary>Lorem</summary>\n\nipsum\n\n</details>\n<!-- dolor -->");
+    let expected = String::from(
+        "<details>\n<summary>Lorem</summary>\n<p>ipsum</p>\n</details>\n<!-- dolor -->\n",
+    );
+
+    assert_eq!(rustdown::parse(input), expected);
+}
+
+#[test]
+fn it_parses_emphasis_and_strong_emphasis() {
+    let input =
+        String::from("# *Lorem* __ipsum__\n***dolor** sit* amet_consectetur_ * adipiscing*");
+    let expected = String::from(
+        "<h1><em>Lorem</em> <strong>ipsum</strong></h1>\n<p><em><strong>dolor</strong> sit</em> amet_consectetur_ * adipiscing*</p>\n",
+    );
+
+    assert_eq!(rustdown::parse(input), expected);
+}
+
+#[test]
+fn it_parses_links_and_images() {
+    let input =
+        String::from("[**Lorem**](<https://example.com/a b> 'ipsum') ![dolor *sit*](/amet.png)");
+    let expected = String::from(
+        "<p><a href=\"https://example.com/a%20b\" title=\"ipsum\"><strong>Lorem</strong></a> <img src=\"/amet.png\" alt=\"dolor sit\" /></p>\n",
+    );
+
+    assert_eq!(rustdown::parse(input), expected);
+}
+
+#[test]
+fn it_parses_reference_links() {
+    let input = String::from(
+        "[Lorem][ipsum], [Dolor][] and ![sit]\n\n[ipsum]: /ipsum\n[dolor]: </dolor> \"Dolor\"\n[SIT]: /sit.png",
+    );
+    let expected = String::from(
+        "<p><a href=\"/ipsum\">Lorem</a>, <a href=\"/dolor\" title=\"Dolor\">Dolor</a> and <img src=\"/sit.png\" alt=\"sit\" /></p>\n",
+    );
+
+    assert_eq!(rustdown::parse(input), expected);
+}
+
+#[test]
+fn it_decodes_backslash_escapes_and_character_references() {
+    let input = String::from(
+        "\\*Lorem\\* &copy; &#x263A; `&amp;`\n[ipsum](/f&ouml;&ouml; \"\\\"dolor\\\"\")\n\n``` rust&#32;ignore\n```",
+    );
+    let expected = String::from(
+        "<p>*Lorem* © ☺ <code>&amp;amp;</code>\n<a href=\"/f%C3%B6%C3%B6\" title=\"&quot;dolor&quot;\">ipsum</a></p>\n<pre><code class=\"language-rust\"></code></pre>\n",
+    );
+
+    assert_eq!(rustdown::parse(input), expected);
+}
+
+#[test]
+fn it_parses_autolinks_and_raw_html() {
+    let input = String::from(
+        "<https://example.com/lorem> <ipsum@example.com>\n<span class=\"dolor\">*sit*</span><!-- amet -->",
+    );
+    let expected = String::from(
+        "<p><a href=\"https://example.com/lorem\">https://example.com/lorem</a> <a href=\"mailto:ipsum@example.com\">ipsum@example.com</a>\n<span class=\"dolor\"><em>sit</em></span><!-- amet --></p>\n",
+    );
+
+    assert_eq!(rustdown::parse(input), expected);
+}
+
+#[test]
+fn it_parses_hard_and_soft_line_breaks() {
+    let input = String::from("Lorem  \nipsum\\\r\ndolor \r  sit\namet  \n");
+    let expected = String::from("<p>Lorem<br />\nipsum<br />\ndolor\nsit\namet</p>\n");
+
+    assert_eq!(rustdown::parse(input), expected);
+}
+
+#[test]
+fn it_expands_tabs_to_four_column_tab_stops() {
+    let input = String::from("-\tLorem\n\n\t\tipsum\n\n>\t\tdolor\n\n*\t*\t*\n");
+    let expected = String::from(
+        "<ul>\n<li>\n<p>Lorem</p>\n<pre><code>ipsum\n</code></pre>\n</li>\n</ul>\n<blockquote>\n<pre><code>  dolor\n</code></pre>\n</blockquote>\n<hr />\n",
+    );
+
+    assert_eq!(rustdown::parse(input), expected);
+}
+
+#[test]
+fn it_parses_nested_containers_with_lazy_continuation_lines() {
+    let input = String::from("> 1. > Lorem\nipsum\n>\n>    - dolor\n>\n>      sit\n> 2. amet\n");
+    let expected = String::from(
+        "<blockquote>\n<ol>\n<li>\n<blockquote>\n<p>Lorem\nipsum</p>\n</blockquote>\n<ul>\n<li>\n<p>dolor</p>\n<p>sit</p>\n</li>\n</ul>\n</li>\n<li>\n<p>amet</p>\n</li>\n</ol>\n</blockquote>\n",
+    );
+
+    assert_eq!(rustdown::parse(input), expected);
+}
+
+#[test]
+fn it_parses_bytes_with_byte_order_mark_and_invalid_utf8() {
+    let input = b"\xef\xbb\xbfLorem\r\nips\xffum\r\n\r\n    dolor\x00\rsit\n";
+    let expected = String::from(
+        "<p>Lorem\nips\u{FFFD}um</p>\n<pre><code>dolor\u{FFFD}\n</code></pre>\n<p>sit</p>\n",
+    );
+
+    assert_eq!(rustdown::parse_bytes(input), expected);
+}
+
+#[test]
+fn it_escapes_html_special_characters() {
+    let input = String::from(
+        "Lorem < ipsum & \"dolor\" `<sit>` [amet](/a?b=1&c=\"2\" \"<t>\") <b>raw</b>\n\n    a > b\n\n```x<y\n&\n```\n\n<div>\n\"</div>",
+    );
+    let expected = String::from(
+        "<p>Lorem &lt; ipsum &amp; &quot;dolor&quot; <code>&lt;sit&gt;</code> <a href=\"/a?b=1&amp;c=%222%22\" title=\"&lt;t&gt;\">amet</a> <b>raw</b></p>\n<pre><code>a &gt; b\n</code></pre>\n<pre><code class=\"language-x&lt;y\">&amp;\n</code></pre>\n<div>\n\"</div>\n",
+    );
+
+    assert_eq!(rustdown::parse(input), expected);
+}
+
+#[test]
+fn it_renders_untrusted_input_in_safe_mode() {
+    let mut document = rustdown::document::Document::new();
+    document.set_safe_mode(true);
+
+    let input = String::from(
+        "<script>alert(1)</script>\n\nLorem <img src=x onerror=alert(1)> [ipsum](JavaScript:alert(1)) <vbscript:msgbox>",
+    );
+    let expected = String::from(
+        "<!-- raw HTML omitted -->\n<p>Lorem <!-- raw HTML omitted --> <a href=\"\">ipsum</a> <a href=\"\">vbscript:msgbox</a></p>\n",
+    );
+
+    assert_eq!(document.render(input), expected);
+}
+
+#[test]
+fn it_sanitizes_raw_html_with_policy() {
+    let mut document = rustdown::document::Document::new();
+    document.set_sanitizer_policy(Some(rustdown::document::SanitizerPolicy::new()));
+
+    let input = String::from(
+        "<details>\n<summary>Lorem</summary>\n\nPress <kbd>Ctrl</kbd> and <sup>2 <script>x</script>\n\n<img src=\"javascript:x\" alt=\"ipsum\" onerror=\"x\">\n\n</details>",
+    );
+    let expected = String::from(
+        "<details>\n<summary>Lorem</summary>\n<p>Press <kbd>Ctrl</kbd> and <sup>2 &lt;script&gt;x&lt;/script&gt;</sup></p>\n<img alt=\"ipsum\" />\n</details>\n",
+    );
+
+    assert_eq!(document.render(input), expected);
+}
+
+#[test]
+fn it_renders_in_compact_and_pretty_output_formats() {
+    let input = String::from("***\n- Lorem\n\n  ipsum");
+
+    let mut document = rustdown::document::Document::new();
+
+    document.set_output_format(rustdown::document::OutputFormat::Compact);
+    assert_eq!(
+        document.render(input.clone()),
+        "<hr /><ul><li><p>Lorem</p><p>ipsum</p></li></ul>"
+    );
+
+    document.set_output_format(rustdown::document::OutputFormat::Pretty);
+    assert_eq!(
+        document.render(input),
+        "<hr />\n<ul>\n  <li>\n    <p>Lorem</p>\n    <p>ipsum</p>\n  </li>\n</ul>\n"
+    );
+}
+
+#[test]
+fn it_renders_void_elements_in_html5_style() {
+    let mut document = rustdown::document::Document::new();
+    document.set_void_element_style(rustdown::document::VoidElementStyle::Html5);
+    document.set_sanitizer_policy(Some(rustdown::document::SanitizerPolicy::new()));
+
+    let input = String::from("***\nLorem  \n![ipsum](/dolor.png) <br/>");
+    let expected =
+        String::from("<hr>\n<p>Lorem<br>\n<img src=\"/dolor.png\" alt=\"ipsum\"> <br></p>\n");
+
+    assert_eq!(document.render(input), expected);
+}
+
+#[test]
+fn it_parses_into_syntax_tree_that_can_be_changed_before_rendering() {
+    use rustdown::document::{Block, Container, Document, InlineCategory, LeafCategory};
+
+    let mut document = Document::new();
+    let mut block_structure = document.parse(String::from(
+        "# Lorem\n\n> [ipsum][1]\n\n```rust\ndolor\n```\n\n[1]: http://example.com\n",
+    ));
+
+    let Block::Leaf(heading) = &block_structure[0] else {
+        panic!("expected a heading, got {:?}", block_structure[0]);
+    };
+    assert_eq!(
+        heading.category,
+        LeafCategory::AtxHeading(rustdown::document::AtxHeadingLevel::One)
+    );
+
+    let Block::Leaf(code_block) = &block_structure[2] else {
+        panic!("expected a code block, got {:?}", block_structure[2]);
+    };
+    assert_eq!(
+        code_block.category,
+        LeafCategory::FencedCodeBlock(Some(String::from("rust")))
+    );
+
+    let Block::Container(Container::BlockQuote(child_blocks), _) = &mut block_structure[1] else {
+        panic!("expected a block quote, got {:?}", block_structure[1]);
+    };
+    let Block::Leaf(paragraph) = &mut child_blocks[0] else {
+        panic!("expected a paragraph, got {:?}", child_blocks[0]);
+    };
+    let InlineCategory::Link(destination, _, _) = &mut paragraph.inlines[0].category else {
+        panic!("expected a link, got {:?}", paragraph.inlines[0]);
+    };
+    assert_eq!(destination, "http://example.com");
+    *destination = String::from("https://example.com");
+
+    assert_eq!(
+        document.render_block_structure(block_structure),
+        "<h1>Lorem</h1>\n<blockquote>\n<p><a href=\"https://example.com\">ipsum</a></p>\n</blockquote>\n<pre><code class=\"language-rust\">dolor\n</code></pre>\n"
+    );
+}
+
+#[test]
+fn it_locates_syntax_tree_nodes_in_the_input() {
+    use rustdown::document::{Block, Container, Document};
+
+    let mut document = Document::new();
+    let block_structure = document.parse_bytes(b"# Lorem\r\n\r\n- ipsum *dolor*\r\n");
+
+    let lines_and_columns = |block: &Block| {
+        let span = block.span();
+
+        (
+            (span.start.line, span.start.column),
+            (span.end.line, span.end.column),
+        )
+    };
+
+    assert_eq!(lines_and_columns(&block_structure[0]), ((1, 1), (1, 8)));
+    assert_eq!(lines_and_columns(&block_structure[1]), ((3, 1), (3, 16)));
+
+    let Block::Container(Container::List(_, _, list_items), _) = &block_structure[1] else {
+        panic!("expected a list, got {:?}", block_structure[1]);
+    };
+    let Block::Container(Container::ListItem(child_blocks), _) = &list_items[0] else {
+        panic!("expected a list item, got {:?}", list_items[0]);
+    };
+    let Block::Leaf(paragraph) = &child_blocks[0] else {
+        panic!("expected a paragraph, got {:?}", child_blocks[0]);
+    };
+    let emphasis_span = paragraph.inlines[1].span;
+
+    assert_eq!(emphasis_span.start.offset, 19);
+    assert_eq!(
+        (emphasis_span.start.column, emphasis_span.end.column),
+        (9, 16)
+    );
+}